@@ -52,6 +52,7 @@ pub mod demand_manager {
         demand.max_price_per_hour = max_price_per_hour;
         demand.total_budget = total_budget;
         demand.preferred_regions = preferred_regions;
+        demand.job_spec = Pubkey::default();
         demand.status = DemandStatus::Open;
         demand.created_at = Clock::get()?.unix_timestamp;
         demand.updated_at = Clock::get()?.unix_timestamp;
@@ -67,6 +68,69 @@ pub mod demand_manager {
         Ok(())
     }
 
+    // Attach the job to run to an open demand. The spec cannot be changed
+    // afterwards, so matches made against it fix exactly what is executed.
+    pub fn create_job_spec(
+        ctx: Context<CreateJobSpec>,
+        image_digest: [u8; 32],
+        command_hash: [u8; 32],
+        input_cids: Vec<String>,
+        output_location: String,
+        max_cores: u16,
+        max_memory_gb: u16,
+        max_wall_clock_secs: u64,
+    ) -> Result<()> {
+        let demand = &mut ctx.accounts.demand;
+        let job_spec = &mut ctx.accounts.job_spec;
+        let consumer = &ctx.accounts.consumer;
+        
+        // Verify the consumer is the demand creator
+        require!(
+            demand.consumer == consumer.key(),
+            DemandError::UnauthorizedJobSpec
+        );
+        
+        require!(
+            demand.status == DemandStatus::Open,
+            DemandError::DemandNotOpen
+        );
+        
+        require!(
+            input_cids.len() <= MAX_JOB_INPUTS
+                && input_cids.iter().all(|cid| cid.len() <= MAX_CID_LEN)
+                && output_location.len() <= MAX_OUTPUT_LOCATION_LEN,
+            DemandError::InvalidJobSpec
+        );
+        
+        require!(
+            max_wall_clock_secs > 0,
+            DemandError::InvalidJobSpec
+        );
+        
+        job_spec.demand = demand.key();
+        job_spec.image_digest = image_digest;
+        job_spec.command_hash = command_hash;
+        job_spec.input_cids = input_cids;
+        job_spec.output_location = output_location;
+        job_spec.max_cores = max_cores;
+        job_spec.max_memory_gb = max_memory_gb;
+        job_spec.max_wall_clock_secs = max_wall_clock_secs;
+        job_spec.bump = *ctx.bumps.get("job_spec").unwrap();
+        job_spec.created_at = Clock::get()?.unix_timestamp;
+        
+        demand.job_spec = job_spec.key();
+        demand.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(JobSpecCreated {
+            job_spec: job_spec.key(),
+            demand_id: demand.key(),
+            image_digest,
+            command_hash,
+        });
+
+        Ok(())
+    }
+
     // Update demand status
     pub fn update_demand_status(
         ctx: Context<UpdateDemandStatus>,
@@ -164,6 +228,23 @@ pub struct PublishDemand<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CreateJobSpec<'info> {
+    #[account(mut)]
+    pub demand: Account<'info, Demand>,
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + JobSpec::LEN,
+        seeds = [b"job_spec".as_ref(), demand.key().as_ref()],
+        bump
+    )]
+    pub job_spec: Account<'info, JobSpec>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateDemandStatus<'info> {
     #[account(mut)]
@@ -200,15 +281,44 @@ pub struct Demand {
     pub max_price_per_hour: u64,           // Maximum price willing to pay per hour
     pub total_budget: u64,                 // Total budget for the computation
     pub preferred_regions: Vec<String>,    // Preferred geographical regions
+    pub job_spec: Pubkey,                  // Job to run (default until attached)
     pub status: DemandStatus,              // Current status
     pub created_at: i64,                   // Creation timestamp
     pub updated_at: i64,                   // Last update timestamp
 }
 
 impl Demand {
-    pub const LEN: usize = 512; // Approximation, should be calculated precisely in production
+    pub const LEN: usize = 544; // Approximation, should be calculated precisely in production
+}
+
+#[account]
+pub struct JobSpec {
+    pub demand: Pubkey,                    // Demand the job belongs to
+    pub image_digest: [u8; 32],            // Container image digest (sha256)
+    pub command_hash: [u8; 32],            // Hash of the entrypoint and arguments
+    pub input_cids: Vec<String>,           // Content IDs of the input datasets
+    pub output_location: String,           // Where results are expected to be written
+    pub max_cores: u16,                    // CPU core limit
+    pub max_memory_gb: u16,                // Memory limit in GB
+    pub max_wall_clock_secs: u64,          // Maximum run time
+    pub bump: u8,                          // JobSpec PDA bump
+    pub created_at: i64,                   // Creation timestamp
 }
 
+impl JobSpec {
+    pub const LEN: usize = 32 + 32 + 32 + 4 + MAX_JOB_INPUTS * (4 + MAX_CID_LEN)
+        + 4 + MAX_OUTPUT_LOCATION_LEN + 2 + 2 + 8 + 1 + 8;
+}
+
+// Maximum number of input datasets per job
+pub const MAX_JOB_INPUTS: usize = 8;
+
+// Longest input content ID
+pub const MAX_CID_LEN: usize = 64;
+
+// Longest output location
+pub const MAX_OUTPUT_LOCATION_LEN: usize = 128;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceType {
     CPU,
@@ -236,6 +346,14 @@ pub struct DemandPublished {
     pub total_budget: u64,
 }
 
+#[event]
+pub struct JobSpecCreated {
+    pub job_spec: Pubkey,
+    pub demand_id: Pubkey,
+    pub image_digest: [u8; 32],
+    pub command_hash: [u8; 32],
+}
+
 #[event]
 pub struct DemandStatusUpdated {
     pub demand_id: Pubkey,
@@ -269,4 +387,10 @@ pub enum DemandError {
     UnauthorizedCancellation,
     #[msg("Cannot cancel a demand that has already been matched or is in progress")]
     CannotCancelFulfilledDemand,
+    #[msg("Only the demand creator can attach a job spec")]
+    UnauthorizedJobSpec,
+    #[msg("Job spec can only be attached to an open demand")]
+    DemandNotOpen,
+    #[msg("Job spec exceeds size limits or has no run time")]
+    InvalidJobSpec,
 } 
\ No newline at end of file
@@ -1,6 +1,10 @@
 // matching.rs - Resource Matching Contract for Calctra Platform
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
 use anchor_spl::token::{self, TokenAccount};
+use demand_manager::{Demand, JobSpec};
+use resource_manager::{ProviderProfile, Resource};
 
 declare_id!("CALMtch7YRx6nHFg3PZx8vQ9Dw2KeLjyhwRMMbmGj4");
 
@@ -8,7 +12,8 @@ declare_id!("CALMtch7YRx6nHFg3PZx8vQ9Dw2KeLjyhwRMMbmGj4");
 pub mod matching_engine {
     use super::*;
 
-    // Match a demand with a resource
+    // Match a demand with a resource. The demand must already have a job
+    // spec, and the resource and time window must fit its limits.
     pub fn create_match(
         ctx: Context<CreateMatch>,
         start_time: i64,
@@ -17,38 +22,36 @@ pub mod matching_engine {
         total_price: u64,
         match_score: u8,
         escrow_amount: u64,
+        acceptance_deadline: i64,
     ) -> Result<()> {
+        let params = MatchParams {
+            start_time,
+            end_time,
+            price_per_hour,
+            total_price,
+            match_score,
+            escrow_amount,
+            acceptance_deadline,
+        };
+        validate_match_params(&params, &ctx.accounts.resource, &ctx.accounts.job_spec)?;
+        validate_match_parties(
+            &ctx.accounts.demand,
+            &ctx.accounts.resource,
+            ctx.accounts.consumer.key(),
+            ctx.accounts.provider.key(),
+        )?;
+
         let match_record = &mut ctx.accounts.match_record;
-        let demand = &ctx.accounts.demand;
-        let resource = &ctx.accounts.resource;
-        let matcher = &ctx.accounts.matcher;
-        
-        // Validate time parameters
-        require!(
-            end_time > start_time,
-            MatchingError::InvalidTimeRange
-        );
-        
-        // Validate price
-        require!(
-            price_per_hour > 0 && total_price > 0,
-            MatchingError::InvalidPrice
-        );
-        
-        match_record.demand = demand.key();
-        match_record.resource = resource.key();
-        match_record.consumer = *ctx.accounts.consumer.key;
-        match_record.provider = *ctx.accounts.provider.key;
-        match_record.matcher = matcher.key();
-        match_record.start_time = start_time;
-        match_record.end_time = end_time;
-        match_record.price_per_hour = price_per_hour;
-        match_record.total_price = total_price;
-        match_record.match_score = match_score;
-        match_record.escrow_amount = escrow_amount;
-        match_record.status = MatchStatus::Created;
-        match_record.created_at = Clock::get()?.unix_timestamp;
-        match_record.updated_at = Clock::get()?.unix_timestamp;
+        init_match(
+            match_record,
+            &params,
+            ctx.accounts.demand.key(),
+            &ctx.accounts.resource,
+            ctx.accounts.consumer.key(),
+            ctx.accounts.provider.key(),
+            ctx.accounts.matcher.key(),
+        )?;
+        commit_job_spec(match_record, &ctx.accounts.job_spec)?;
         
         emit!(MatchCreated {
             match_id: match_record.key(),
@@ -62,10 +65,116 @@ pub mod matching_engine {
         Ok(())
     }
 
+    // Match several demand/resource pairs in one transaction.
+    // remaining_accounts holds MATCH_BATCH_ACCOUNTS entries per pair:
+    // [match_record (signer, writable), demand, resource, consumer, provider,
+    //  demand's job spec PDA]
+    pub fn create_match_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateMatchBatch<'info>>,
+        params: Vec<MatchParams>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        
+        require!(
+            !params.is_empty() && params.len() <= MAX_BATCH_MATCHES,
+            MatchingError::InvalidBatchSize
+        );
+        require!(
+            remaining.len() == params.len() * MATCH_BATCH_ACCOUNTS,
+            MatchingError::InvalidBatchAccounts
+        );
+        
+        // Validate every entry before creating any account so a bad pair
+        // rejects the whole round
+        let mut loaded: Vec<(Account<Resource>, Account<JobSpec>)> = Vec::with_capacity(params.len());
+        for (i, entry) in params.iter().enumerate() {
+            let accounts = &remaining[i * MATCH_BATCH_ACCOUNTS..(i + 1) * MATCH_BATCH_ACCOUNTS];
+            
+            let (job_spec_address, _) = Pubkey::find_program_address(
+                &[b"job_spec".as_ref(), accounts[1].key.as_ref()],
+                &demand_manager::ID,
+            );
+            require!(
+                accounts[5].key() == job_spec_address,
+                MatchingError::InvalidJobSpec
+            );
+            
+            let demand: Account<Demand> = Account::try_from(&accounts[1])?;
+            let resource: Account<Resource> = Account::try_from(&accounts[2])?;
+            let job_spec: Account<JobSpec> = Account::try_from(&accounts[5])?;
+            validate_match_params(entry, &resource, &job_spec)?;
+            validate_match_parties(&demand, &resource, accounts[3].key(), accounts[4].key())?;
+            loaded.push((resource, job_spec));
+            
+            let match_info = &accounts[0];
+            require!(
+                match_info.is_signer && match_info.is_writable,
+                MatchingError::InvalidBatchAccounts
+            );
+            require!(
+                match_info.owner == &System::id() && match_info.data_is_empty(),
+                MatchingError::MatchAccountInUse
+            );
+            
+            for j in 0..i {
+                require!(
+                    remaining[j * MATCH_BATCH_ACCOUNTS].key != match_info.key,
+                    MatchingError::DuplicateBatchEntry
+                );
+            }
+        }
+        
+        let space = 8 + Match::LEN;
+        let lamports = Rent::get()?.minimum_balance(space);
+        
+        for (i, (entry, (resource, job_spec))) in params.iter().zip(loaded.iter()).enumerate() {
+            let accounts = &remaining[i * MATCH_BATCH_ACCOUNTS..(i + 1) * MATCH_BATCH_ACCOUNTS];
+            let match_info = &accounts[0];
+            
+            system_program::create_account(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::CreateAccount {
+                        from: ctx.accounts.matcher.to_account_info(),
+                        to: match_info.clone(),
+                    },
+                ),
+                lamports,
+                space as u64,
+                ctx.program_id,
+            )?;
+            
+            let mut match_record: Account<Match> = Account::try_from_unchecked(match_info)?;
+            init_match(
+                &mut match_record,
+                entry,
+                accounts[1].key(),
+                resource,
+                accounts[3].key(),
+                accounts[4].key(),
+                ctx.accounts.matcher.key(),
+            )?;
+            commit_job_spec(&mut match_record, job_spec)?;
+            match_record.exit(ctx.program_id)?;
+            
+            emit!(MatchCreated {
+                match_id: match_record.key(),
+                demand: match_record.demand,
+                resource: match_record.resource,
+                consumer: match_record.consumer,
+                provider: match_record.provider,
+                total_price: match_record.total_price,
+            });
+        }
+
+        Ok(())
+    }
+
     // Accept a match (by consumer)
     pub fn accept_match_consumer(ctx: Context<AcceptMatchConsumer>) -> Result<()> {
         let match_record = &mut ctx.accounts.match_record;
         let consumer = &ctx.accounts.consumer;
+        let current_time = Clock::get()?.unix_timestamp;
         
         // Verify the consumer matches the demand's consumer
         require!(
@@ -79,8 +188,14 @@ pub mod matching_engine {
             MatchingError::InvalidMatchStatus
         );
         
+        // A match past its acceptance deadline can only be expired
+        require!(
+            current_time <= match_record.acceptance_deadline,
+            MatchingError::AcceptanceDeadlinePassed
+        );
+        
         match_record.status = MatchStatus::ConsumerAccepted;
-        match_record.updated_at = Clock::get()?.unix_timestamp;
+        match_record.updated_at = current_time;
         
         emit!(MatchStatusUpdated {
             match_id: match_record.key(),
@@ -90,10 +205,14 @@ pub mod matching_engine {
         Ok(())
     }
 
-    // Accept a match (by provider)
+    // Accept a match (by provider). A provider with a registered encryption
+    // key passes its profile, and the key is fixed on the match so the
+    // consumer's payload key is always wrapped to the key the provider
+    // accepted with. Without a profile no payload key can be published.
     pub fn accept_match_provider(ctx: Context<AcceptMatchProvider>) -> Result<()> {
         let match_record = &mut ctx.accounts.match_record;
         let provider = &ctx.accounts.provider;
+        let current_time = Clock::get()?.unix_timestamp;
         
         // Verify the provider matches the resource's provider
         require!(
@@ -107,8 +226,19 @@ pub mod matching_engine {
             MatchingError::InvalidMatchStatus
         );
         
+        // A match past its acceptance deadline can only be expired
+        require!(
+            current_time <= match_record.acceptance_deadline,
+            MatchingError::AcceptanceDeadlinePassed
+        );
+        
         match_record.status = MatchStatus::Confirmed;
-        match_record.updated_at = Clock::get()?.unix_timestamp;
+        match_record.recipient_key = ctx
+            .accounts
+            .provider_profile
+            .as_ref()
+            .map_or([0u8; 32], |profile| profile.encryption_key);
+        match_record.updated_at = current_time;
         
         emit!(MatchStatusUpdated {
             match_id: match_record.key(),
@@ -147,10 +277,41 @@ pub mod matching_engine {
         Ok(())
     }
 
-    // Complete a match
-    pub fn complete_match(ctx: Context<CompleteMatch>) -> Result<()> {
+    // Expire a match that was not accepted by both parties in time.
+    // Anyone can call this; rent is returned to the matcher. Payload keys
+    // are only published for confirmed matches, so there is none to close.
+    pub fn expire_match(ctx: Context<ExpireMatch>) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        // Only matches still waiting for acceptance can expire
+        require!(
+            match_record.status == MatchStatus::Created ||
+            match_record.status == MatchStatus::ConsumerAccepted,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        require!(
+            current_time > match_record.acceptance_deadline,
+            MatchingError::AcceptanceDeadlineNotReached
+        );
+        
+        match_record.status = MatchStatus::Expired;
+        match_record.updated_at = current_time;
+        
+        emit!(MatchStatusUpdated {
+            match_id: match_record.key(),
+            status: match_record.status,
+        });
+
+        Ok(())
+    }
+
+    // Start work on a confirmed match (by provider, optionally co-signed by consumer)
+    pub fn start_match(ctx: Context<StartMatch>) -> Result<()> {
         let match_record = &mut ctx.accounts.match_record;
         let provider = &ctx.accounts.provider;
+        let current_time = Clock::get()?.unix_timestamp;
         
         // Verify the provider matches the resource's provider
         require!(
@@ -158,12 +319,316 @@ pub mod matching_engine {
             MatchingError::UnauthorizedProviderAction
         );
         
-        // Only confirmed matches can be completed
+        // Only confirmed matches can be started
         require!(
             match_record.status == MatchStatus::Confirmed,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        // A consumer co-signature records that both parties agree on the start time
+        if let Some(consumer) = &ctx.accounts.consumer {
+            require!(
+                match_record.consumer == consumer.key(),
+                MatchingError::UnauthorizedConsumerAction
+            );
+            match_record.start_acknowledged = true;
+        }
+        
+        match_record.status = MatchStatus::InProgress;
+        match_record.actual_start = current_time;
+        match_record.updated_at = current_time;
+        
+        emit!(MatchStarted {
+            match_id: match_record.key(),
+            actual_start: match_record.actual_start,
+            start_acknowledged: match_record.start_acknowledged,
+        });
+
+        Ok(())
+    }
+
+    // Report a match as finished (by provider) with a commitment to its
+    // result: the hash of the output and execution log. The consumer can
+    // acknowledge or dispute until the challenge window closes.
+    pub fn complete_match(ctx: Context<CompleteMatch>, result_hash: [u8; 32]) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let provider = &ctx.accounts.provider;
+        
+        // Verify the provider matches the resource's provider
+        require!(
+            match_record.provider == provider.key(),
+            MatchingError::UnauthorizedProviderAction
+        );
+        
+        // Only matches in progress can be completed
+        require!(
+            match_record.status == MatchStatus::InProgress,
             MatchingError::InvalidMatchStatusForCompletion
         );
         
+        require!(
+            result_hash != [0u8; 32],
+            MatchingError::InvalidResultHash
+        );
+        
+        // A job that overran its spec's wall-clock limit can still report
+        // its result; settle_usage never bills past the limit
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        match_record.status = MatchStatus::PendingCompletion;
+        match_record.result_hash = result_hash;
+        match_record.actual_end = current_time;
+        match_record.challenge_deadline = current_time + CHALLENGE_WINDOW;
+        match_record.updated_at = current_time;
+        
+        // The provider no longer needs the input data, so drop the payload key
+        clear_payload_key(
+            match_record.key(),
+            &ctx.accounts.payload_key,
+            &ctx.accounts.consumer,
+        )?;
+        
+        emit!(MatchStatusUpdated {
+            match_id: match_record.key(),
+            status: match_record.status,
+        });
+        
+        emit!(ResultCommitted {
+            match_id: match_record.key(),
+            provider: provider.key(),
+            result_hash,
+        });
+
+        Ok(())
+    }
+
+    // Share the input data encryption key with the matched provider (by
+    // consumer). The key is wrapped to the x25519 key recorded on the match
+    // when the provider accepted: ECDH with `ephemeral_key`, then
+    // XChaCha20-Poly1305 under `nonce`. The key is closed again once the
+    // match completes, ends early, is disputed or the provider never shows.
+    pub fn publish_payload_key(
+        ctx: Context<PublishPayloadKey>,
+        ephemeral_key: [u8; 32],
+        nonce: [u8; 24],
+        wrapped_key: [u8; 48],
+    ) -> Result<()> {
+        let match_record = &ctx.accounts.match_record;
+        let payload_key = &mut ctx.accounts.payload_key;
+        
+        require!(
+            match_record.consumer == ctx.accounts.consumer.key(),
+            MatchingError::UnauthorizedConsumerAction
+        );
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        // The provider must have accepted with an encryption key
+        require!(
+            match_record.recipient_key != [0u8; 32],
+            MatchingError::MissingRecipientKey
+        );
+        
+        payload_key.match_id = match_record.key();
+        payload_key.recipient_key = match_record.recipient_key;
+        payload_key.ephemeral_key = ephemeral_key;
+        payload_key.nonce = nonce;
+        payload_key.wrapped_key = wrapped_key;
+        payload_key.bump = *ctx.bumps.get("payload_key").unwrap();
+        payload_key.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(PayloadKeyPublished {
+            match_id: match_record.key(),
+            provider: match_record.provider,
+            recipient_key: payload_key.recipient_key,
+        });
+
+        Ok(())
+    }
+
+    // Run a demand redundantly on several resources (by consumer).
+    // remaining_accounts holds the replica matches (writable), all for this
+    // demand, on distinct resources and not yet accepted by either party.
+    // Their escrows only pay out once `quorum` replicas have committed the
+    // same result, or once the group expires without a quorum.
+    pub fn create_replication_group<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateReplicationGroup<'info>>,
+        quorum: u8,
+    ) -> Result<()> {
+        let group = &mut ctx.accounts.group;
+        let consumer = &ctx.accounts.consumer;
+        let replicas = ctx.remaining_accounts;
+        
+        require!(
+            replicas.len() >= 2 && replicas.len() <= MAX_REPLICAS,
+            MatchingError::InvalidReplicaCount
+        );
+        
+        // A quorum must be a strict majority so only one result can win
+        require!(
+            quorum as usize > replicas.len() / 2 && quorum as usize <= replicas.len(),
+            MatchingError::InvalidQuorum
+        );
+        
+        let mut matches: Vec<Pubkey> = Vec::with_capacity(replicas.len());
+        let mut resources: Vec<Pubkey> = Vec::with_capacity(replicas.len());
+        for info in replicas.iter() {
+            let mut replica: Account<Match> = Account::try_from(info)?;
+            
+            require!(
+                replica.demand == ctx.accounts.demand.key()
+                    && replica.consumer == consumer.key(),
+                MatchingError::InvalidReplica
+            );
+            
+            // Replicas must join before the provider accepts, so providers
+            // know their payout depends on a quorum
+            require!(
+                replica.status == MatchStatus::Created
+                    && replica.replication_group == Pubkey::default(),
+                MatchingError::InvalidReplica
+            );
+            
+            require!(
+                !matches.contains(&info.key()) && !resources.contains(&replica.resource),
+                MatchingError::DuplicateBatchEntry
+            );
+            
+            replica.replication_group = group.key();
+            replica.exit(ctx.program_id)?;
+            
+            matches.push(info.key());
+            resources.push(replica.resource);
+        }
+        
+        group.demand = ctx.accounts.demand.key();
+        group.consumer = consumer.key();
+        group.matches = matches;
+        group.quorum = quorum;
+        group.consensus_hash = [0u8; 32];
+        group.finalized = false;
+        group.failed = false;
+        group.bump = *ctx.bumps.get("group").unwrap();
+        group.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ReplicationGroupCreated {
+            group_id: group.key(),
+            demand: group.demand,
+            matches: group.matches.clone(),
+            quorum,
+        });
+
+        Ok(())
+    }
+
+    // Settle a replication group once a quorum of replicas has committed the
+    // same result. Anyone can call this. remaining_accounts holds every
+    // replica match in group order. Reported replicas that committed a
+    // different result are flagged as outvoted; their escrows stay frozen
+    // until the consumer disputes them through transaction_manager.
+    pub fn finalize_replication<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeReplication<'info>>,
+    ) -> Result<()> {
+        let group = &mut ctx.accounts.group;
+        
+        require!(
+            !group.finalized && !group.failed,
+            MatchingError::ReplicationFinalized
+        );
+        
+        let replicas = load_replicas(group, ctx.remaining_accounts)?;
+        let (consensus_hash, agreeing) =
+            leading_result(&replicas).ok_or(MatchingError::QuorumNotReached)?;
+        
+        require!(
+            agreeing >= group.quorum as usize,
+            MatchingError::QuorumNotReached
+        );
+        
+        group.consensus_hash = consensus_hash;
+        group.finalized = true;
+        
+        for replica in replicas.iter() {
+            let reported = replica.status == MatchStatus::PendingCompletion
+                || replica.status == MatchStatus::Completed;
+            if reported
+                && replica.result_hash != [0u8; 32]
+                && replica.result_hash != consensus_hash
+            {
+                emit!(ReplicaOutvoted {
+                    match_id: replica.key(),
+                    group_id: group.key(),
+                });
+            }
+        }
+        
+        emit!(ReplicationFinalized {
+            group_id: group.key(),
+            consensus_hash,
+            agreeing: agreeing as u8,
+        });
+
+        Ok(())
+    }
+
+    // Give up on a replication group that has not reached a quorum
+    // REPLICATION_TIMEOUT after its last replica was scheduled to end.
+    // Anyone can call this. remaining_accounts holds every replica match in
+    // group order. Each replica's escrow then settles on its own, or through
+    // a dispute.
+    pub fn expire_replication<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeReplication<'info>>,
+    ) -> Result<()> {
+        let group = &mut ctx.accounts.group;
+        
+        require!(
+            !group.finalized && !group.failed,
+            MatchingError::ReplicationFinalized
+        );
+        
+        let replicas = load_replicas(group, ctx.remaining_accounts)?;
+        
+        // Extensions move the end time, so the deadline follows the replicas
+        let last_end = replicas.iter().map(|r| r.end_time).max().unwrap_or(0);
+        require!(
+            Clock::get()?.unix_timestamp > last_end + REPLICATION_TIMEOUT,
+            MatchingError::ReplicationTimeoutNotReached
+        );
+        
+        let agreeing = leading_result(&replicas).map_or(0, |(_, count)| count);
+        require!(
+            agreeing < group.quorum as usize,
+            MatchingError::QuorumReached
+        );
+        
+        group.failed = true;
+        
+        emit!(ReplicationExpired {
+            group_id: group.key(),
+            agreeing: agreeing as u8,
+        });
+
+        Ok(())
+    }
+
+    // Acknowledge a reported completion (by consumer)
+    pub fn acknowledge_completion(ctx: Context<AcknowledgeCompletion>) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let consumer = &ctx.accounts.consumer;
+        
+        require!(
+            match_record.consumer == consumer.key(),
+            MatchingError::UnauthorizedConsumerAction
+        );
+        
+        require!(
+            match_record.status == MatchStatus::PendingCompletion,
+            MatchingError::InvalidMatchStatus
+        );
+        
         match_record.status = MatchStatus::Completed;
         match_record.updated_at = Clock::get()?.unix_timestamp;
         
@@ -174,20 +639,408 @@ pub mod matching_engine {
 
         Ok(())
     }
+
+    // Finalize a reported completion once the challenge window has passed.
+    // Anyone can call this.
+    pub fn finalize_completion(ctx: Context<FinalizeCompletion>) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            match_record.status == MatchStatus::PendingCompletion,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        require!(
+            current_time > match_record.challenge_deadline,
+            MatchingError::ChallengeWindowOpen
+        );
+        
+        match_record.status = MatchStatus::Completed;
+        match_record.updated_at = current_time;
+        
+        emit!(MatchStatusUpdated {
+            match_id: match_record.key(),
+            status: match_record.status,
+        });
+
+        Ok(())
+    }
+
+    // Dispute a match on behalf of the consumer or provider. A reported
+    // completion can only be disputed during its challenge window, unless
+    // its replication group outvoted the committed result. Only
+    // callable through CPI from transaction_manager's dispute_escrow, so the
+    // match and its escrow always enter the dispute together.
+    pub fn dispute_match(
+        ctx: Context<DisputeMatch>,
+        disputed_by: Pubkey,
+        reason: String,
+    ) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            match_record.consumer == disputed_by || match_record.provider == disputed_by,
+            MatchingError::UnauthorizedDispute
+        );
+        
+        let outvoted = match ctx.accounts.replication_group.as_ref() {
+            Some(group) => {
+                group.finalized
+                    && match_record.result_hash != [0u8; 32]
+                    && match_record.result_hash != group.consensus_hash
+            }
+            None => false,
+        };
+        
+        if outvoted {
+            // The escrow was never paid out, so the result stays disputable
+            require!(
+                match_record.status == MatchStatus::PendingCompletion ||
+                match_record.status == MatchStatus::Completed,
+                MatchingError::InvalidMatchStatus
+            );
+        } else {
+            require!(
+                match_record.status == MatchStatus::Confirmed ||
+                match_record.status == MatchStatus::InProgress ||
+                match_record.status == MatchStatus::PendingCompletion,
+                MatchingError::InvalidMatchStatus
+            );
+            
+            if match_record.status == MatchStatus::PendingCompletion {
+                require!(
+                    current_time <= match_record.challenge_deadline,
+                    MatchingError::ChallengeWindowClosed
+                );
+            }
+        }
+        
+        match_record.status = MatchStatus::Disputed;
+        match_record.updated_at = current_time;
+        
+        // Work stops while the match is disputed
+        clear_payload_key(
+            match_record.key(),
+            &ctx.accounts.payload_key,
+            &ctx.accounts.consumer,
+        )?;
+        
+        emit!(MatchDisputed {
+            match_id: match_record.key(),
+            disputed_by,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    // Close a dispute once transaction_manager has settled the escrow.
+    // Only callable through CPI signed by transaction_manager's match authority.
+    pub fn resolve_match_dispute(
+        ctx: Context<ResolveMatchDispute>,
+        consumer_share: u8,
+        provider_share: u8,
+    ) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        
+        require!(
+            match_record.status == MatchStatus::Disputed,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        match_record.status = MatchStatus::Resolved;
+        match_record.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(MatchDisputeResolved {
+            match_id: match_record.key(),
+            consumer_share,
+            provider_share,
+        });
+
+        Ok(())
+    }
+
+    // Move a match's end time out and add the price of the extra time (by
+    // transaction_manager once the provider accepts a funded extension)
+    pub fn extend_match(
+        ctx: Context<ExtendMatch>,
+        new_end_time: i64,
+        added_price: u64,
+    ) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed
+                || match_record.status == MatchStatus::InProgress,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        require!(
+            new_end_time > match_record.end_time,
+            MatchingError::InvalidTimeRange
+        );
+        
+        match_record.end_time = new_end_time;
+        match_record.total_price = match_record
+            .total_price
+            .checked_add(added_price)
+            .ok_or(MatchingError::PriceOverflow)?;
+        if match_record.escrow_amount > 0 {
+            match_record.escrow_amount = match_record
+                .escrow_amount
+                .checked_add(added_price)
+                .ok_or(MatchingError::PriceOverflow)?;
+        }
+        match_record.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(MatchExtended {
+            match_id: match_record.key(),
+            end_time: match_record.end_time,
+            total_price: match_record.total_price,
+        });
+
+        Ok(())
+    }
+
+    // Record that a match was ended early (by transaction_manager once the
+    // escrow has been settled pro rata)
+    pub fn mark_terminated(ctx: Context<MarkTerminated>, terminated_by: Pubkey) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed
+                || match_record.status == MatchStatus::InProgress,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        match_record.status = MatchStatus::Terminated;
+        match_record.actual_end = current_time;
+        match_record.updated_at = current_time;
+        
+        clear_payload_key(
+            match_record.key(),
+            &ctx.accounts.payload_key,
+            &ctx.accounts.consumer,
+        )?;
+        
+        emit!(MatchTerminated {
+            match_id: match_record.key(),
+            terminated_by,
+            actual_end: match_record.actual_end,
+        });
+
+        Ok(())
+    }
+
+    // Record that the provider never started a confirmed match (by
+    // transaction_manager once the consumer has been refunded)
+    pub fn mark_no_show(ctx: Context<MarkNoShow>) -> Result<()> {
+        let match_record = &mut ctx.accounts.match_record;
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed,
+            MatchingError::InvalidMatchStatus
+        );
+        
+        match_record.status = MatchStatus::NoShow;
+        match_record.updated_at = Clock::get()?.unix_timestamp;
+        
+        clear_payload_key(
+            match_record.key(),
+            &ctx.accounts.payload_key,
+            &ctx.accounts.consumer,
+        )?;
+        
+        emit!(MatchNoShow {
+            match_id: match_record.key(),
+            provider: match_record.provider,
+        });
+
+        Ok(())
+    }
+}
+
+fn validate_match_params(params: &MatchParams, resource: &Resource, job_spec: &JobSpec) -> Result<()> {
+    // Validate time parameters
+    require!(
+        params.end_time > params.start_time,
+        MatchingError::InvalidTimeRange
+    );
+    
+    // Validate price
+    require!(
+        params.price_per_hour > 0 && params.total_price > 0,
+        MatchingError::InvalidPrice
+    );
+    
+    // Both parties need some time to respond
+    require!(
+        params.acceptance_deadline > Clock::get()?.unix_timestamp,
+        MatchingError::InvalidAcceptanceDeadline
+    );
+    
+    // The resource and the booked window must fit the job spec's limits
+    require!(
+        resource.cores >= job_spec.max_cores && resource.memory_gb >= job_spec.max_memory_gb,
+        MatchingError::ResourceBelowJobSpec
+    );
+    require!(
+        (params.end_time - params.start_time) as u64 >= job_spec.max_wall_clock_secs,
+        MatchingError::WindowBelowJobSpec
+    );
+
+    Ok(())
+}
+
+// A match can only pair the demand's consumer with the resource's provider
+fn validate_match_parties(
+    demand: &Demand,
+    resource: &Resource,
+    consumer: Pubkey,
+    provider: Pubkey,
+) -> Result<()> {
+    require_keys_eq!(consumer, demand.consumer, MatchingError::ConsumerMismatch);
+    require_keys_eq!(provider, resource.provider, MatchingError::ProviderMismatch);
+
+    Ok(())
+}
+
+// Load a replication group's replica matches from remaining_accounts,
+// which must list them all in group order. Replicas closed after expiring
+// unaccepted are skipped.
+fn load_replicas<'info>(
+    group: &ReplicationGroup,
+    infos: &[AccountInfo<'info>],
+) -> Result<Vec<Account<'info, Match>>> {
+    require!(
+        infos.len() == group.matches.len(),
+        MatchingError::InvalidReplica
+    );
+    
+    let mut replicas: Vec<Account<Match>> = Vec::with_capacity(group.matches.len());
+    for (info, expected) in infos.iter().zip(group.matches.iter()) {
+        require!(
+            info.key() == *expected,
+            MatchingError::InvalidReplica
+        );
+        
+        // A replica that expired unaccepted was closed and never commits
+        if info.data_is_empty() {
+            continue;
+        }
+        replicas.push(Account::try_from(info)?);
+    }
+    
+    Ok(replicas)
+}
+
+// Find the result committed by the most replicas and how many committed it
+fn leading_result(replicas: &[Account<Match>]) -> Option<([u8; 32], usize)> {
+    let committed: Vec<[u8; 32]> = replicas
+        .iter()
+        .filter(|r| r.result_hash != [0u8; 32])
+        .map(|r| r.result_hash)
+        .collect();
+    committed
+        .iter()
+        .map(|h| (*h, committed.iter().filter(|other| *other == h).count()))
+        .max_by_key(|(_, count)| *count)
+}
+
+// Close the match's payload key, if the consumer published one, returning
+// its rent to the consumer
+fn clear_payload_key<'info>(
+    match_id: Pubkey,
+    payload_key: &UncheckedAccount<'info>,
+    consumer: &AccountInfo<'info>,
+) -> Result<()> {
+    let payload_key_info = payload_key.to_account_info();
+    if payload_key_info.data_is_empty() {
+        return Ok(());
+    }
+    
+    let payload_key: Account<PayloadKey> = Account::try_from(&payload_key_info)?;
+    payload_key.close(consumer.clone())?;
+    
+    emit!(PayloadKeyCleared { match_id });
+
+    Ok(())
+}
+
+// Commit the match to the demand's job spec by its address and a hash of
+// its contents, and record the wall-clock limit enforced at completion
+fn commit_job_spec(match_record: &mut Match, job_spec: &Account<JobSpec>) -> Result<()> {
+    let info = job_spec.to_account_info();
+    let data = info.try_borrow_data()?;
+    
+    match_record.job_spec = job_spec.key();
+    match_record.job_spec_hash = hashv(&[&data]).to_bytes();
+    match_record.max_wall_clock_secs = job_spec.max_wall_clock_secs;
+
+    Ok(())
+}
+
+fn init_match(
+    match_record: &mut Match,
+    params: &MatchParams,
+    demand: Pubkey,
+    resource: &Account<Resource>,
+    consumer: Pubkey,
+    provider: Pubkey,
+    matcher: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    
+    match_record.demand = demand;
+    match_record.resource = resource.key();
+    match_record.consumer = consumer;
+    match_record.provider = provider;
+    match_record.matcher = matcher;
+    match_record.start_time = params.start_time;
+    match_record.end_time = params.end_time;
+    match_record.price_per_hour = params.price_per_hour;
+    match_record.total_price = params.total_price;
+    match_record.match_score = params.match_score;
+    match_record.escrow_amount = params.escrow_amount;
+    match_record.acceptance_deadline = params.acceptance_deadline;
+    match_record.status = MatchStatus::Created;
+    match_record.result_hash = [0u8; 32];
+    match_record.replication_group = Pubkey::default();
+    match_record.min_rental_hours = resource.min_rental_time_hours;
+    match_record.payment_mint = resource.payment_mint;
+    match_record.recipient_key = [0u8; 32];
+    match_record.created_at = now;
+    match_record.updated_at = now;
+
+    Ok(())
 }
 
 #[derive(Accounts)]
 pub struct CreateMatch<'info> {
     #[account(init, payer = matcher, space = 8 + Match::LEN)]
     pub match_record: Account<'info, Match>,
-    /// CHECK: We only use this for reading public key
-    pub demand: AccountInfo<'info>,
-    /// CHECK: We only use this for reading public key
-    pub resource: AccountInfo<'info>,
+    pub demand: Account<'info, Demand>,
+    pub resource: Account<'info, Resource>,
     /// CHECK: We only use this for reading public key
     pub consumer: AccountInfo<'info>,
     /// CHECK: We only use this for reading public key
     pub provider: AccountInfo<'info>,
+    #[account(
+        seeds = [b"job_spec".as_ref(), demand.key().as_ref()],
+        bump = job_spec.bump,
+        seeds::program = demand_manager::ID
+    )]
+    pub job_spec: Account<'info, JobSpec>,
+    #[account(mut)]
+    pub matcher: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMatchBatch<'info> {
     #[account(mut)]
     pub matcher: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -205,6 +1058,13 @@ pub struct AcceptMatchProvider<'info> {
     #[account(mut)]
     pub match_record: Account<'info, Match>,
     pub provider: Signer<'info>,
+    // Only needed to pin the provider's encryption key
+    #[account(
+        seeds = [b"provider_profile".as_ref(), match_record.provider.as_ref()],
+        bump = provider_profile.bump,
+        seeds::program = resource_manager::ID
+    )]
+    pub provider_profile: Option<Account<'info, ProviderProfile>>,
 }
 
 #[derive(Accounts)]
@@ -214,11 +1074,187 @@ pub struct RejectMatch<'info> {
     pub signer: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ExpireMatch<'info> {
+    #[account(mut, has_one = matcher, close = matcher)]
+    pub match_record: Account<'info, Match>,
+    /// CHECK: Receives the rent of the closed match, checked via has_one
+    #[account(mut)]
+    pub matcher: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartMatch<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    pub provider: Signer<'info>,
+    pub consumer: Option<Signer<'info>>,
+}
+
 #[derive(Accounts)]
 pub struct CompleteMatch<'info> {
     #[account(mut)]
     pub match_record: Account<'info, Match>,
     pub provider: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist
+    pub payload_key: UncheckedAccount<'info>,
+    #[account(mut, address = match_record.consumer)]
+    /// CHECK: Receives the payload key rent
+    pub consumer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PublishPayloadKey<'info> {
+    pub match_record: Account<'info, Match>,
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + PayloadKey::LEN,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    pub payload_key: Account<'info, PayloadKey>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateReplicationGroup<'info> {
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + ReplicationGroup::LEN,
+        seeds = [b"replication".as_ref(), demand.key().as_ref()],
+        bump
+    )]
+    pub group: Account<'info, ReplicationGroup>,
+    /// CHECK: We only use this for reading public key
+    pub demand: AccountInfo<'info>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReplication<'info> {
+    #[account(
+        mut,
+        seeds = [b"replication".as_ref(), group.demand.as_ref()],
+        bump = group.bump
+    )]
+    pub group: Account<'info, ReplicationGroup>,
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeCompletion<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    pub consumer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeCompletion<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeMatch<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        seeds = [b"match_authority".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub match_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist
+    pub payload_key: UncheckedAccount<'info>,
+    #[account(mut, address = match_record.consumer)]
+    /// CHECK: Receives the payload key rent
+    pub consumer: AccountInfo<'info>,
+    // Only needed to dispute a replica its group outvoted
+    #[account(address = match_record.replication_group @ MatchingError::InvalidReplica)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMatchDispute<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        seeds = [b"match_authority".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub match_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendMatch<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        seeds = [b"match_authority".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub match_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkTerminated<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        seeds = [b"match_authority".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub match_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist
+    pub payload_key: UncheckedAccount<'info>,
+    #[account(mut, address = match_record.consumer)]
+    /// CHECK: Receives the payload key rent
+    pub consumer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkNoShow<'info> {
+    #[account(mut)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        seeds = [b"match_authority".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub match_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist
+    pub payload_key: UncheckedAccount<'info>,
+    #[account(mut, address = match_record.consumer)]
+    /// CHECK: Receives the payload key rent
+    pub consumer: AccountInfo<'info>,
 }
 
 #[account]
@@ -234,13 +1270,92 @@ pub struct Match {
     pub total_price: u64,                // Total price for the match
     pub match_score: u8,                 // Match score (0-100)
     pub escrow_amount: u64,              // Amount in escrow for the match
+    pub acceptance_deadline: i64,        // Both parties must accept before this time
     pub status: MatchStatus,             // Current status of the match
+    pub actual_start: i64,               // When work actually started (0 if not started)
+    pub actual_end: i64,                 // When work actually finished (0 if not finished)
+    pub start_acknowledged: bool,        // Whether the consumer co-signed the start
+    pub challenge_deadline: i64,         // Consumer can dispute a reported completion until this time
+    pub result_hash: [u8; 32],           // Provider's commitment to the output and execution log
+    pub replication_group: Pubkey,       // Replication group the match belongs to (default if none)
+    pub job_spec: Pubkey,                // Demand's job spec at match time
+    pub job_spec_hash: [u8; 32],         // Hash of the job spec contents both parties agreed to
+    pub max_wall_clock_secs: u64,        // Job spec's run time limit, caps the billed hours
+    pub min_rental_hours: u16,           // Resource's minimum billable hours when the match was made
+    pub payment_mint: Pubkey,            // Mint the price was quoted in when the match was made
+    pub recipient_key: [u8; 32],         // Provider x25519 key recorded when the provider accepted
     pub created_at: i64,                 // Creation timestamp
     pub updated_at: i64,                 // Last update timestamp
 }
 
 impl Match {
-    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 1 + 8 + 8;
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 1 + 8 + 8 + 1 + 8 + 32 + 32 + 32 + 32 + 8 + 2 + 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct PayloadKey {
+    pub match_id: Pubkey,                // Match the input data belongs to
+    pub recipient_key: [u8; 32],         // Provider x25519 key the DEK is wrapped to
+    pub ephemeral_key: [u8; 32],         // Consumer's ephemeral x25519 public key
+    pub nonce: [u8; 24],                 // AEAD nonce
+    pub wrapped_key: [u8; 48],           // Encrypted 32-byte DEK plus 16-byte tag
+    pub bump: u8,                        // PayloadKey PDA bump
+    pub created_at: i64,                 // Creation timestamp
+}
+
+impl PayloadKey {
+    pub const LEN: usize = 32 + 32 + 32 + 24 + 48 + 1 + 8;
+}
+
+#[account]
+pub struct ReplicationGroup {
+    pub demand: Pubkey,                  // Demand executed redundantly
+    pub consumer: Pubkey,                // Demand owner
+    pub matches: Vec<Pubkey>,            // Replica matches
+    pub quorum: u8,                      // Replicas that must agree on a result
+    pub consensus_hash: [u8; 32],        // Result agreed by the quorum
+    pub finalized: bool,                 // Whether a quorum has been reached
+    pub failed: bool,                    // Whether the group expired without a quorum
+    pub bump: u8,                        // ReplicationGroup PDA bump
+    pub created_at: i64,                 // Creation timestamp
+}
+
+impl ReplicationGroup {
+    pub const LEN: usize = 32 + 32 + 4 + MAX_REPLICAS * 32 + 1 + 32 + 1 + 1 + 1 + 8;
+}
+
+// transaction_manager program, whose match authority PDA settles disputes
+pub const TRANSACTION_MANAGER_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("CALTrxn8HfQVcfq2L9hJHTyvKS8MFALKvHt8DGwsWf");
+
+// Time the consumer has to dispute a completion reported by the provider
+pub const CHALLENGE_WINDOW: i64 = 24 * 60 * 60;
+
+// Maximum number of pairs per batch. Each pair adds about 310 bytes to the
+// 1232-byte transaction (the match account's 64-byte signature, six 32-byte
+// account keys and 49 bytes of params) on top of about 210 bytes of fixed
+// overhead, so only three fit.
+pub const MAX_BATCH_MATCHES: usize = 3;
+
+// Maximum number of replicas in a replication group
+pub const MAX_REPLICAS: usize = 5;
+
+// Time after the last replica's scheduled end before a group without a
+// quorum can be expired
+pub const REPLICATION_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+// Accounts consumed from remaining_accounts for each batched pair
+pub const MATCH_BATCH_ACCOUNTS: usize = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MatchParams {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub price_per_hour: u64,
+    pub total_price: u64,
+    pub match_score: u8,
+    pub escrow_amount: u64,
+    pub acceptance_deadline: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -251,6 +1366,12 @@ pub enum MatchStatus {
     Rejected,
     Completed,
     Disputed,
+    Expired,
+    InProgress,
+    PendingCompletion,
+    Resolved,
+    NoShow,
+    Terminated,
 }
 
 #[event]
@@ -263,12 +1384,99 @@ pub struct MatchCreated {
     pub total_price: u64,
 }
 
+#[event]
+pub struct MatchStarted {
+    pub match_id: Pubkey,
+    pub actual_start: i64,
+    pub start_acknowledged: bool,
+}
+
 #[event]
 pub struct MatchStatusUpdated {
     pub match_id: Pubkey,
     pub status: MatchStatus,
 }
 
+#[event]
+pub struct ResultCommitted {
+    pub match_id: Pubkey,
+    pub provider: Pubkey,
+    pub result_hash: [u8; 32],
+}
+
+#[event]
+pub struct PayloadKeyPublished {
+    pub match_id: Pubkey,
+    pub provider: Pubkey,
+    pub recipient_key: [u8; 32],
+}
+
+#[event]
+pub struct PayloadKeyCleared {
+    pub match_id: Pubkey,
+}
+
+#[event]
+pub struct ReplicationGroupCreated {
+    pub group_id: Pubkey,
+    pub demand: Pubkey,
+    pub matches: Vec<Pubkey>,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct ReplicationFinalized {
+    pub group_id: Pubkey,
+    pub consensus_hash: [u8; 32],
+    pub agreeing: u8,
+}
+
+#[event]
+pub struct ReplicationExpired {
+    pub group_id: Pubkey,
+    pub agreeing: u8,
+}
+
+#[event]
+pub struct ReplicaOutvoted {
+    pub match_id: Pubkey,
+    pub group_id: Pubkey,
+}
+
+#[event]
+pub struct MatchDisputed {
+    pub match_id: Pubkey,
+    pub disputed_by: Pubkey,
+    pub reason: String,
+}
+
+#[event]
+pub struct MatchExtended {
+    pub match_id: Pubkey,
+    pub end_time: i64,
+    pub total_price: u64,
+}
+
+#[event]
+pub struct MatchTerminated {
+    pub match_id: Pubkey,
+    pub terminated_by: Pubkey,
+    pub actual_end: i64,
+}
+
+#[event]
+pub struct MatchNoShow {
+    pub match_id: Pubkey,
+    pub provider: Pubkey,
+}
+
+#[event]
+pub struct MatchDisputeResolved {
+    pub match_id: Pubkey,
+    pub consumer_share: u8,
+    pub provider_share: u8,
+}
+
 #[error_code]
 pub enum MatchingError {
     #[msg("End time must be after start time")]
@@ -285,6 +1493,56 @@ pub enum MatchingError {
     InvalidMatchStatus,
     #[msg("Cannot reject a match that is already in progress or completed")]
     CannotRejectActiveMatch,
-    #[msg("Only confirmed matches can be completed")]
+    #[msg("Only matches in progress can be completed")]
     InvalidMatchStatusForCompletion,
+    #[msg("Batch must contain between 1 and 3 entries")]
+    InvalidBatchSize,
+    #[msg("Remaining accounts do not match the batch layout")]
+    InvalidBatchAccounts,
+    #[msg("Match account is already in use")]
+    MatchAccountInUse,
+    #[msg("The same match account appears twice in the batch")]
+    DuplicateBatchEntry,
+    #[msg("Acceptance deadline must be in the future")]
+    InvalidAcceptanceDeadline,
+    #[msg("Match cannot be expired before its acceptance deadline")]
+    AcceptanceDeadlineNotReached,
+    #[msg("The completion challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("The completion challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Only the consumer or provider can dispute this match")]
+    UnauthorizedDispute,
+    #[msg("Result hash must not be empty")]
+    InvalidResultHash,
+    #[msg("Invalid number of replicas")]
+    InvalidReplicaCount,
+    #[msg("Quorum must be a majority of the replicas")]
+    InvalidQuorum,
+    #[msg("Match cannot be used as a replica in this group")]
+    InvalidReplica,
+    #[msg("Replication group has already been finalized or expired")]
+    ReplicationFinalized,
+    #[msg("Not enough replicas agree on a result")]
+    QuorumNotReached,
+    #[msg("Job spec account does not belong to the demand")]
+    InvalidJobSpec,
+    #[msg("The acceptance deadline has passed")]
+    AcceptanceDeadlinePassed,
+    #[msg("The replication timeout has not passed")]
+    ReplicationTimeoutNotReached,
+    #[msg("A quorum of replicas agrees; finalize the group instead")]
+    QuorumReached,
+    #[msg("Resource has fewer cores or less memory than the job spec requires")]
+    ResourceBelowJobSpec,
+    #[msg("Match window is shorter than the job spec's wall-clock limit")]
+    WindowBelowJobSpec,
+    #[msg("Consumer does not own the demand")]
+    ConsumerMismatch,
+    #[msg("Provider does not own the resource")]
+    ProviderMismatch,
+    #[msg("Extended price overflows")]
+    PriceOverflow,
+    #[msg("Provider accepted the match without an encryption key")]
+    MissingRecipientKey,
 } 
\ No newline at end of file
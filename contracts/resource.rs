@@ -1,14 +1,22 @@
 // resource.rs - Resource Management Contract for Calctra Platform
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TokenAccount};
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token::{self, Mint, TokenAccount};
 
 declare_id!("CALRsrce5gHmzW8bpPvqQhCTf7Zpyy6wdxfKdQwZMDj");
 
+// transaction_manager program, which keeps the payment mint allow-list
+pub const TRANSACTION_MANAGER_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("CALTrxn8HfQVcfq2L9hJHTyvKS8MFALKvHt8DGwsWf");
+
 #[program]
 pub mod resource_manager {
     use super::*;
 
-    // Register a new computing resource
+    // Register a new computing resource. The provider must have a profile,
+    // which carries their reputation across all of their resources, so no
+    // resource can be listed outside the no-show record. The profile does
+    // not need an encryption key.
     pub fn register_resource(
         ctx: Context<RegisterResource>,
         name: String,
@@ -26,6 +34,8 @@ pub mod resource_manager {
         let resource = &mut ctx.accounts.resource;
         let provider = &ctx.accounts.provider;
         
+        require_mint_enabled(&ctx.accounts.allowed_mint)?;
+        
         resource.name = name;
         resource.resource_type = resource_type;
         resource.cores = cores;
@@ -33,6 +43,7 @@ pub mod resource_manager {
         resource.storage_gb = storage_gb;
         resource.provider = provider.key();
         resource.price_per_hour = price_per_hour;
+        resource.payment_mint = ctx.accounts.payment_mint.key();
         resource.min_rental_time_hours = min_rental_time_hours;
         resource.max_rental_time_hours = max_rental_time_hours;
         resource.region = region;
@@ -49,6 +60,7 @@ pub mod resource_manager {
             name: resource.name.clone(),
             resource_type: resource.resource_type,
             price_per_hour: resource.price_per_hour,
+            payment_mint: resource.payment_mint,
         });
 
         Ok(())
@@ -79,7 +91,7 @@ pub mod resource_manager {
         Ok(())
     }
 
-    // Update resource pricing
+    // Update resource pricing, optionally switching to another allow-listed mint
     pub fn update_resource_pricing(
         ctx: Context<UpdateResourcePricing>,
         price_per_hour: u64,
@@ -95,7 +107,10 @@ pub mod resource_manager {
             ResourceError::UnauthorizedPricingUpdate
         );
         
+        require_mint_enabled(&ctx.accounts.allowed_mint)?;
+        
         resource.price_per_hour = price_per_hour;
+        resource.payment_mint = ctx.accounts.payment_mint.key();
         resource.min_rental_time_hours = min_rental_time_hours;
         resource.max_rental_time_hours = max_rental_time_hours;
         resource.updated_at = Clock::get()?.unix_timestamp;
@@ -103,6 +118,7 @@ pub mod resource_manager {
         emit!(ResourcePricingUpdated {
             resource_id: resource.key(),
             price_per_hour,
+            payment_mint: resource.payment_mint,
             min_rental_time_hours,
             max_rental_time_hours,
         });
@@ -110,6 +126,76 @@ pub mod resource_manager {
         Ok(())
     }
 
+    // Penalize a provider who failed to start a confirmed match (by
+    // transaction_manager when the consumer claims a no-show refund). The
+    // penalty is kept on the provider's profile so deleting and
+    // re-registering a resource does not clear it.
+    pub fn record_no_show(ctx: Context<RecordNoShow>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        
+        profile.reputation_score = profile.reputation_score.saturating_sub(NO_SHOW_PENALTY);
+        profile.no_show_count += 1;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ProviderReputationUpdated {
+            provider: profile.provider,
+            reputation_score: profile.reputation_score,
+            no_show_count: profile.no_show_count,
+        });
+
+        Ok(())
+    }
+
+    // Start the provider's reputation record and register the x25519 public
+    // key consumers wrap job payload keys to. A provider that does not take
+    // encrypted payloads can leave the key zeroed and set it later.
+    pub fn register_provider_profile(
+        ctx: Context<RegisterProviderProfile>,
+        encryption_key: [u8; 32],
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        
+        let now = Clock::get()?.unix_timestamp;
+        profile.provider = ctx.accounts.provider.key();
+        profile.encryption_key = encryption_key;
+        profile.reputation_score = MAX_REPUTATION;
+        profile.no_show_count = 0;
+        profile.bump = *ctx.bumps.get("profile").unwrap();
+        profile.created_at = now;
+        profile.updated_at = now;
+        
+        emit!(EncryptionKeyUpdated {
+            provider: profile.provider,
+            encryption_key,
+        });
+
+        Ok(())
+    }
+
+    // Rotate the provider's encryption key. Payload keys already published
+    // record the key they were wrapped to.
+    pub fn update_encryption_key(
+        ctx: Context<UpdateEncryptionKey>,
+        encryption_key: [u8; 32],
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        
+        require!(
+            encryption_key != [0u8; 32],
+            ResourceError::InvalidEncryptionKey
+        );
+        
+        profile.encryption_key = encryption_key;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(EncryptionKeyUpdated {
+            provider: profile.provider,
+            encryption_key,
+        });
+
+        Ok(())
+    }
+
     // Delete a resource
     pub fn delete_resource(ctx: Context<DeleteResource>) -> Result<()> {
         let resource = &ctx.accounts.resource;
@@ -130,10 +216,42 @@ pub mod resource_manager {
     }
 }
 
+// Check that transaction_manager's allow-list entry for a mint is enabled.
+// The account's owner and seeds are checked by the accounts struct.
+fn require_mint_enabled(allowed_mint: &AccountInfo) -> Result<()> {
+    let data = allowed_mint.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == hash(b"account:AllowedMint").to_bytes()[..8],
+        ResourceError::MintNotAllowed
+    );
+    
+    let entry = AllowedMintPrefix::deserialize(&mut &data[8..])?;
+    require!(
+        entry.enabled,
+        ResourceError::MintNotAllowed
+    );
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct RegisterResource<'info> {
     #[account(init, payer = provider, space = 8 + Resource::LEN)]
     pub resource: Account<'info, Resource>,
+    pub payment_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), payment_mint.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID,
+        owner = TRANSACTION_MANAGER_ID @ ResourceError::MintNotAllowed
+    )]
+    /// CHECK: transaction_manager's allow-list entry, read by require_mint_enabled
+    pub allowed_mint: AccountInfo<'info>,
+    #[account(
+        seeds = [b"provider_profile".as_ref(), provider.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, ProviderProfile>,
     #[account(mut)]
     pub provider: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -150,6 +268,15 @@ pub struct UpdateResourceStatus<'info> {
 pub struct UpdateResourcePricing<'info> {
     #[account(mut)]
     pub resource: Account<'info, Resource>,
+    pub payment_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), payment_mint.key().as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID,
+        owner = TRANSACTION_MANAGER_ID @ ResourceError::MintNotAllowed
+    )]
+    /// CHECK: transaction_manager's allow-list entry, read by require_mint_enabled
+    pub allowed_mint: AccountInfo<'info>,
     pub provider: Signer<'info>,
 }
 
@@ -161,6 +288,48 @@ pub struct DeleteResource<'info> {
     pub provider: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct RegisterProviderProfile<'info> {
+    #[account(
+        init,
+        payer = provider,
+        space = 8 + ProviderProfile::LEN,
+        seeds = [b"provider_profile".as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, ProviderProfile>,
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEncryptionKey<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider_profile".as_ref(), provider.key().as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, ProviderProfile>,
+    pub provider: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordNoShow<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider_profile".as_ref(), profile.provider.as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, ProviderProfile>,
+    #[account(
+        seeds = [b"reputation_authority".as_ref()],
+        bump,
+        seeds::program = TRANSACTION_MANAGER_ID
+    )]
+    pub reputation_authority: Signer<'info>,
+}
+
 #[account]
 pub struct Resource {
     pub name: String,                      // Resource name
@@ -169,7 +338,8 @@ pub struct Resource {
     pub memory_gb: u16,                    // RAM in GB
     pub storage_gb: u32,                   // Storage in GB
     pub provider: Pubkey,                  // Resource provider address
-    pub price_per_hour: u64,               // Price per hour in lamports
+    pub price_per_hour: u64,               // Price per hour in base units of payment_mint (lamports for wSOL)
+    pub payment_mint: Pubkey,              // Allow-listed mint the price is quoted in
     pub min_rental_time_hours: u16,        // Minimum rental duration
     pub max_rental_time_hours: u16,        // Maximum rental duration
     pub region: String,                    // Geographical region
@@ -182,9 +352,41 @@ pub struct Resource {
 }
 
 impl Resource {
-    pub const LEN: usize = 256; // Approximation, should be calculated precisely in production
+    pub const LEN: usize = 288; // Approximation, should be calculated precisely in production
+}
+
+#[account]
+pub struct ProviderProfile {
+    pub provider: Pubkey,                  // Provider address
+    pub encryption_key: [u8; 32],          // x25519 public key for payload key wrapping
+    pub reputation_score: u16,             // Provider reliability (0-10000)
+    pub no_show_count: u32,                // Confirmed matches the provider never started
+    pub bump: u8,                          // ProviderProfile PDA bump
+    pub created_at: i64,                   // Creation timestamp
+    pub updated_at: i64,                   // Last update timestamp
+}
+
+impl ProviderProfile {
+    pub const LEN: usize = 32 + 32 + 2 + 4 + 1 + 8 + 8;
 }
 
+// Leading fields of transaction_manager's AllowedMint account. Kept here
+// because transaction_manager depends on this crate.
+#[derive(AnchorDeserialize)]
+pub struct AllowedMintPrefix {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub rewards_pool: Pubkey,
+    pub enabled: bool,
+}
+
+// Reputation of a newly registered provider
+pub const MAX_REPUTATION: u16 = 10_000;
+
+// Reputation lost for each no-show
+pub const NO_SHOW_PENALTY: u16 = 1_000;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceType {
     CPU,
@@ -201,6 +403,13 @@ pub enum ResourceStatus {
     Offline,
 }
 
+#[event]
+pub struct ProviderReputationUpdated {
+    pub provider: Pubkey,
+    pub reputation_score: u16,
+    pub no_show_count: u32,
+}
+
 #[event]
 pub struct ResourceRegistered {
     pub resource_id: Pubkey,
@@ -208,6 +417,7 @@ pub struct ResourceRegistered {
     pub name: String,
     pub resource_type: ResourceType,
     pub price_per_hour: u64,
+    pub payment_mint: Pubkey,
 }
 
 #[event]
@@ -220,10 +430,17 @@ pub struct ResourceStatusUpdated {
 pub struct ResourcePricingUpdated {
     pub resource_id: Pubkey,
     pub price_per_hour: u64,
+    pub payment_mint: Pubkey,
     pub min_rental_time_hours: u16,
     pub max_rental_time_hours: u16,
 }
 
+#[event]
+pub struct EncryptionKeyUpdated {
+    pub provider: Pubkey,
+    pub encryption_key: [u8; 32],
+}
+
 #[event]
 pub struct ResourceDeleted {
     pub resource_id: Pubkey,
@@ -238,4 +455,8 @@ pub enum ResourceError {
     UnauthorizedPricingUpdate,
     #[msg("Only the resource provider can delete this resource")]
     UnauthorizedDeletion,
+    #[msg("Payment mint is not on the allow-list")]
+    MintNotAllowed,
+    #[msg("Encryption key must not be empty")]
+    InvalidEncryptionKey,
 } 
\ No newline at end of file
@@ -1,6 +1,16 @@
 // transaction.rs - Transaction Management Contract for Calctra Platform
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::slot_hashes;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{self, CloseAccount, Mint, SyncNative, Token, TokenAccount, Transfer};
+use crate::program::TransactionManager;
+use matching_engine::program::MatchingEngine;
+use matching_engine::{Match, MatchStatus, ReplicationGroup};
+use resource_manager::program::ResourceManager;
+use resource_manager::{ProviderProfile, Resource, ResourceStatus};
 
 declare_id!("CALTrxn8HfQVcfq2L9hJHTyvKS8MFALKvHt8DGwsWf");
 
@@ -8,15 +18,292 @@ declare_id!("CALTrxn8HfQVcfq2L9hJHTyvKS8MFALKvHt8DGwsWf");
 pub mod transaction_manager {
     use super::*;
 
-    // Create an escrow transaction
+    // Initialize the program configuration (by the program's upgrade
+    // authority, who becomes the admin)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, usage_oracle: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        config.admin = ctx.accounts.admin.key();
+        config.usage_oracle = usage_oracle;
+        config.default_consumer_share = 50;
+        config.bump = *ctx.bumps.get("config").unwrap();
+        
+        emit!(ConfigInitialized {
+            admin: config.admin,
+            usage_oracle: config.usage_oracle,
+        });
+
+        Ok(())
+    }
+
+    // Allow-list a payment mint (by admin). Creates the mint's treasury and
+    // insurance fund vaults and records the stakers' rewards pool that
+    // receives a share of platform fees paid in this mint.
+    pub fn add_allowed_mint(ctx: Context<AddAllowedMint>) -> Result<()> {
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        allowed_mint.mint = ctx.accounts.mint.key();
+        allowed_mint.treasury = ctx.accounts.treasury.key();
+        allowed_mint.insurance_fund = ctx.accounts.insurance_fund.key();
+        allowed_mint.rewards_pool = ctx.accounts.rewards_pool.key();
+        allowed_mint.enabled = true;
+        allowed_mint.bump = *ctx.bumps.get("allowed_mint").unwrap();
+        
+        emit!(AllowedMintAdded {
+            mint: allowed_mint.mint,
+            treasury: allowed_mint.treasury,
+            insurance_fund: allowed_mint.insurance_fund,
+            rewards_pool: allowed_mint.rewards_pool,
+        });
+
+        Ok(())
+    }
+
+    // Enable or disable an allow-listed mint for new escrows (by admin).
+    // Existing escrows in a disabled mint still settle normally.
+    pub fn set_mint_enabled(ctx: Context<SetMintEnabled>, enabled: bool) -> Result<()> {
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        allowed_mint.enabled = enabled;
+        
+        emit!(AllowedMintUpdated {
+            mint: allowed_mint.mint,
+            enabled,
+        });
+
+        Ok(())
+    }
+
+    // Register an arbitrator (by admin)
+    pub fn add_arbitrator(ctx: Context<AddArbitrator>) -> Result<()> {
+        let arbitrator = &mut ctx.accounts.arbitrator;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            ctx.accounts.config.stake_mint != Pubkey::default(),
+            TransactionError::ArbitrationNotInitialized
+        );
+        
+        arbitrator.authority = ctx.accounts.authority.key();
+        arbitrator.index = ctx.accounts.config.arbitrator_count;
+        arbitrator.active = true;
+        arbitrator.stake = 0;
+        arbitrator.stake_vault = ctx.accounts.stake_vault.key();
+        arbitrator.active_panels = 0;
+        arbitrator.resolved_count = 0;
+        arbitrator.slashed_count = 0;
+        arbitrator.missed_count = 0;
+        arbitrator.bump = *ctx.bumps.get("arbitrator").unwrap();
+        arbitrator.created_at = Clock::get()?.unix_timestamp;
+        
+        ctx.accounts.config.arbitrator_count += 1;
+        
+        emit!(ArbitratorUpdated {
+            authority: arbitrator.authority,
+            active: arbitrator.active,
+        });
+
+        Ok(())
+    }
+
+    // Deactivate an arbitrator (by admin). The record is kept so disputes
+    // already assigned to them can still be traced.
+    pub fn remove_arbitrator(ctx: Context<RemoveArbitrator>) -> Result<()> {
+        let arbitrator = &mut ctx.accounts.arbitrator;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        arbitrator.active = false;
+        
+        emit!(ArbitratorUpdated {
+            authority: arbitrator.authority,
+            active: arbitrator.active,
+        });
+
+        Ok(())
+    }
+
+    // Update the platform fee and how it is split (by admin). Shares are in
+    // basis points of the fee; whatever is not routed elsewhere stays in the treasury.
+    pub fn update_fee_config(
+        ctx: Context<UpdateFeeConfig>,
+        fee_bps: u16,
+        rewards_share_bps: u16,
+        matcher_share_bps: u16,
+        insurance_share_bps: u16,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        require!(
+            config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            fee_bps <= MAX_FEE_BPS,
+            TransactionError::InvalidFee
+        );
+        
+        require!(
+            rewards_share_bps as u32 + matcher_share_bps as u32 + insurance_share_bps as u32
+                <= BPS_DENOMINATOR as u32,
+            TransactionError::InvalidFee
+        );
+        
+        config.fee_bps = fee_bps;
+        config.rewards_share_bps = rewards_share_bps;
+        config.matcher_share_bps = matcher_share_bps;
+        config.insurance_share_bps = insurance_share_bps;
+        
+        emit!(FeeConfigUpdated {
+            fee_bps,
+            rewards_share_bps,
+            matcher_share_bps,
+            insurance_share_bps,
+        });
+
+        Ok(())
+    }
+
+    // Set the split applied when a dispute times out (by admin).
+    // 50 splits evenly; 100 refunds the consumer in full.
+    pub fn set_default_resolution(
+        ctx: Context<UpdateConfig>,
+        default_consumer_share: u8,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        require!(
+            config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            default_consumer_share <= 100,
+            TransactionError::InvalidShares
+        );
+        
+        config.default_consumer_share = default_consumer_share;
+        
+        emit!(DefaultResolutionUpdated {
+            default_consumer_share,
+        });
+
+        Ok(())
+    }
+
+    // Set the fee charged to whichever party terminates a match early (by
+    // admin). It is taken from the unearned part of the escrow and paid to
+    // the other party.
+    pub fn set_cancellation_fee(ctx: Context<UpdateConfig>, cancellation_fee_bps: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        require!(
+            config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            cancellation_fee_bps <= MAX_CANCELLATION_FEE_BPS,
+            TransactionError::InvalidFee
+        );
+        
+        config.cancellation_fee_bps = cancellation_fee_bps;
+        
+        emit!(CancellationFeeUpdated {
+            cancellation_fee_bps,
+        });
+
+        Ok(())
+    }
+
+    // Withdraw from the treasury (by admin)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        
+        require!(
+            config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            amount > 0,
+            TransactionError::InvalidAmount
+        );
+        
+        let seeds = &[b"config".as_ref(), &[config.bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        transfer_from_vault(
+            &ctx.accounts.treasury,
+            &ctx.accounts.destination,
+            &config.to_account_info(),
+            &ctx.accounts.token_program,
+            signer_seeds,
+            amount,
+        )?;
+        
+        emit!(TreasuryWithdrawn {
+            mint: ctx.accounts.treasury.mint,
+            amount,
+            destination: ctx.accounts.destination.key(),
+        });
+
+        Ok(())
+    }
+
+    // Create an escrow transaction for a confirmed match. Clients should send
+    // this in the same transaction as matching_engine's accept_match_provider
+    // so the match is never confirmed without funds behind it.
     pub fn create_escrow(
         ctx: Context<CreateEscrow>,
-        match_id: Pubkey,
-        amount: u64,
         release_time: i64,
+        mode: EscrowMode,
+        milestones: Vec<MilestoneParams>,
     ) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
         let consumer = &ctx.accounts.consumer;
+        let match_record = &ctx.accounts.match_record;
+        
+        // The escrow must fund the match the consumer actually agreed to
+        require!(
+            match_record.consumer == consumer.key(),
+            TransactionError::UnauthorizedEscrowCreation
+        );
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed,
+            TransactionError::MatchNotConfirmed
+        );
+        
+        // Escrows are held in the mint the match was priced in
+        require!(
+            ctx.accounts.allowed_mint.enabled,
+            TransactionError::MintNotAllowed
+        );
+        
+        let amount = if match_record.escrow_amount > 0 {
+            match_record.escrow_amount
+        } else {
+            match_record.total_price
+        };
         
         // Validate amount and time
         require!(
@@ -29,55 +316,82 @@ pub mod transaction_manager {
             TransactionError::InvalidReleaseTime
         );
         
+        // Milestones must be given for milestone escrows only, and must
+        // account for the whole escrow amount
+        if mode == EscrowMode::Milestone {
+            require!(
+                !milestones.is_empty() && milestones.len() <= MAX_MILESTONES,
+                TransactionError::InvalidMilestones
+            );
+            let total = milestones
+                .iter()
+                .try_fold(0u64, |sum, m| sum.checked_add(m.amount))
+                .ok_or(TransactionError::InvalidMilestones)?;
+            require!(
+                total == amount && milestones.iter().all(|m| m.amount > 0),
+                TransactionError::InvalidMilestones
+            );
+        } else {
+            require!(
+                milestones.is_empty(),
+                TransactionError::InvalidMilestones
+            );
+        }
+        
         // Initialize escrow account
-        escrow.match_id = match_id;
+        escrow.match_id = match_record.key();
         escrow.consumer = consumer.key();
-        escrow.provider = ctx.accounts.provider.key();
+        escrow.provider = match_record.provider;
+        escrow.mint = ctx.accounts.mint.key();
         escrow.amount = amount;
+        escrow.released_amount = 0;
+        escrow.refunded_amount = 0;
         escrow.release_time = release_time;
+        escrow.mode = mode;
+        escrow.stream_start = match_record.start_time;
+        escrow.stream_end = match_record.end_time;
+        escrow.milestones = milestones
+            .into_iter()
+            .map(|m| Milestone {
+                amount: m.amount,
+                description_hash: m.description_hash,
+                deadline: m.deadline,
+                status: MilestoneStatus::Pending,
+            })
+            .collect();
         escrow.status = EscrowStatus::Created;
         escrow.created_at = Clock::get()?.unix_timestamp;
         escrow.updated_at = Clock::get()?.unix_timestamp;
         
-        // Transfer tokens from consumer to escrow account
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.consumer_token_account.to_account_info(),
-            to: ctx.accounts.escrow_token_account.to_account_info(),
-            authority: consumer.to_account_info(),
-        };
-        
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        token::transfer(cpi_ctx, amount)?;
+        deposit_to_vault(
+            consumer,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            amount,
+        )?;
         
         emit!(EscrowCreated {
             escrow_id: escrow.key(),
             match_id: escrow.match_id,
             consumer: escrow.consumer,
             provider: escrow.provider,
+            mint: escrow.mint,
             amount: escrow.amount,
         });
 
         Ok(())
     }
 
-    // Release funds from escrow to provider
-    pub fn release_funds(ctx: Context<ReleaseFunds>) -> Result<()> {
+    // Propose running a match past its end time (by consumer). The extra time
+    // is paid for up front into the escrow vault and only added to the escrow
+    // once the provider accepts. If the escrow settles while the proposal
+    // is still pending, the top-up is refunded and the proposal closed.
+    pub fn propose_extension(ctx: Context<ProposeExtension>, new_end_time: i64) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
-        let signer = &ctx.accounts.signer;
-        
-        // Check if release is authorized
-        let current_time = Clock::get()?.unix_timestamp;
-        
-        // Either the consumer can release, or anyone can release after release_time
-        if current_time < escrow.release_time {
-            // Before release time, only consumer can release
-            require!(
-                escrow.consumer == signer.key(),
-                TransactionError::UnauthorizedRelease
-            );
-        }
+        let match_record = &ctx.accounts.match_record;
+        let extension = &mut ctx.accounts.extension;
         
         // Check escrow status
         require!(
@@ -85,52 +399,67 @@ pub mod transaction_manager {
             TransactionError::InvalidEscrowStatus
         );
         
-        // Update escrow status
-        escrow.status = EscrowStatus::Released;
-        escrow.updated_at = current_time;
-        
-        // Transfer funds from escrow to provider
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.provider_token_account.to_account_info(),
-            authority: ctx.accounts.escrow_authority.to_account_info(),
-        };
+        // Milestones fix the payout schedule up front
+        require!(
+            escrow.mode != EscrowMode::Milestone,
+            TransactionError::MilestoneEscrow
+        );
         
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let seeds = &[
-            b"escrow".as_ref(),
-            escrow.key().as_ref(),
-            &[*ctx.bumps.get("escrow_authority").unwrap()],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        require!(
+            match_record.status == MatchStatus::Confirmed
+                || match_record.status == MatchStatus::InProgress,
+            TransactionError::MatchNotActive
+        );
         
-        let cpi_ctx = CpiContext::new_with_signer(
-            cpi_program,
-            cpi_accounts,
-            signer_seeds,
+        require!(
+            new_end_time > match_record.end_time,
+            TransactionError::InvalidExtension
         );
         
-        token::transfer(cpi_ctx, escrow.amount)?;
+        // Price the extra time at the match's hourly rate, rounding up
+        let added_seconds = (new_end_time - match_record.end_time) as u128;
+        let top_up = ((added_seconds * match_record.price_per_hour as u128 + 3599) / 3600) as u64;
         
-        emit!(EscrowReleased {
+        deposit_to_vault(
+            &ctx.accounts.consumer,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            top_up,
+        )?;
+        
+        escrow.extension_pending = true;
+        
+        extension.escrow = escrow.key();
+        extension.new_end_time = new_end_time;
+        extension.top_up = top_up;
+        extension.bump = *ctx.bumps.get("extension").unwrap();
+        extension.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ExtensionProposed {
             escrow_id: escrow.key(),
             match_id: escrow.match_id,
-            amount: escrow.amount,
-            released_by: signer.key(),
+            new_end_time,
+            top_up,
         });
 
         Ok(())
     }
 
-    // Refund escrow to consumer
-    pub fn refund_escrow(ctx: Context<RefundEscrow>) -> Result<()> {
+    // Accept a proposed extension (by provider). The match and escrow are
+    // updated together. The resource must still be in service, but its
+    // later bookings are not tracked on-chain, so overlap with the next
+    // booking is not checked here; accepting is the provider's commitment
+    // that the resource is free for the extra time.
+    pub fn accept_extension(ctx: Context<AcceptExtension>) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
-        let signer = &ctx.accounts.signer;
+        let extension = &ctx.accounts.extension;
+        let match_record = &ctx.accounts.match_record;
         
-        // Either the provider can refund, or arbitrator can refund
         require!(
-            escrow.provider == signer.key() || ctx.accounts.arbitrator.key() == signer.key(),
-            TransactionError::UnauthorizedRefund
+            escrow.provider == ctx.accounts.provider.key(),
+            TransactionError::UnauthorizedExtension
         );
         
         // Check escrow status
@@ -139,251 +468,4333 @@ pub mod transaction_manager {
             TransactionError::InvalidEscrowStatus
         );
         
-        // Update escrow status
-        escrow.status = EscrowStatus::Refunded;
-        escrow.updated_at = Clock::get()?.unix_timestamp;
+        require!(
+            match_record.status == MatchStatus::Confirmed
+                || match_record.status == MatchStatus::InProgress,
+            TransactionError::MatchNotActive
+        );
         
-        // Transfer funds from escrow back to consumer
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.consumer_token_account.to_account_info(),
-            authority: ctx.accounts.escrow_authority.to_account_info(),
-        };
+        // The extended rental must still fit the resource's limits
+        let resource = &ctx.accounts.resource;
+        require!(
+            resource.status == ResourceStatus::Available || resource.status == ResourceStatus::InUse,
+            TransactionError::ResourceUnavailable
+        );
+        require!(
+            extension.new_end_time - match_record.start_time
+                <= resource.max_rental_time_hours as i64 * 3600,
+            TransactionError::ExceedsMaxRentalTime
+        );
         
-        let cpi_program = ctx.accounts.token_program.to_account_info();
+        escrow.amount += extension.top_up;
+        escrow.extension_pending = false;
+        if escrow.mode == EscrowMode::Streaming {
+            escrow.stream_end = extension.new_end_time;
+        }
+        escrow.updated_at = Clock::get()?.unix_timestamp;
+        
+        let match_key = match_record.key();
         let seeds = &[
-            b"escrow".as_ref(),
-            escrow.key().as_ref(),
-            &[*ctx.bumps.get("escrow_authority").unwrap()],
+            b"match_authority".as_ref(),
+            match_key.as_ref(),
+            &[*ctx.bumps.get("match_authority").unwrap()],
         ];
         let signer_seeds = &[&seeds[..]];
         
+        let cpi_accounts = matching_engine::cpi::accounts::ExtendMatch {
+            match_record: match_record.to_account_info(),
+            match_authority: ctx.accounts.match_authority.to_account_info(),
+        };
         let cpi_ctx = CpiContext::new_with_signer(
-            cpi_program,
+            ctx.accounts.matching_program.to_account_info(),
             cpi_accounts,
             signer_seeds,
         );
         
-        token::transfer(cpi_ctx, escrow.amount)?;
+        matching_engine::cpi::extend_match(cpi_ctx, extension.new_end_time, extension.top_up)?;
         
-        emit!(EscrowRefunded {
+        emit!(ExtensionAccepted {
             escrow_id: escrow.key(),
             match_id: escrow.match_id,
+            new_end_time: extension.new_end_time,
             amount: escrow.amount,
-            refunded_by: signer.key(),
         });
 
         Ok(())
     }
 
-    // Dispute an escrow transaction
-    pub fn dispute_escrow(ctx: Context<DisputeEscrow>, reason: String) -> Result<()> {
+    // Withdraw (by consumer) or decline (by provider) a proposed extension,
+    // returning the top-up to the consumer
+    pub fn cancel_extension(ctx: Context<CancelExtension>) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
+        let extension = &ctx.accounts.extension;
         let signer = &ctx.accounts.signer;
         
-        // Either consumer or provider can dispute
         require!(
             escrow.consumer == signer.key() || escrow.provider == signer.key(),
-            TransactionError::UnauthorizedDispute
+            TransactionError::UnauthorizedExtension
         );
         
-        // Check escrow status
-        require!(
-            escrow.status == EscrowStatus::Created,
-            TransactionError::InvalidEscrowStatus
-        );
+        escrow.extension_pending = false;
         
-        // Update escrow status
-        escrow.status = EscrowStatus::Disputed;
-        escrow.updated_at = Clock::get()?.unix_timestamp;
+        let escrow_key = escrow.key();
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow_key.as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
         
-        emit!(EscrowDisputed {
+        transfer_from_vault(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.consumer_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            extension.top_up,
+        )?;
+        
+        emit!(ExtensionCancelled {
             escrow_id: escrow.key(),
-            match_id: escrow.match_id,
-            disputed_by: signer.key(),
-            reason,
+            cancelled_by: signer.key(),
+            top_up: extension.top_up,
         });
 
         Ok(())
     }
 
-    // Resolve a disputed escrow
-    pub fn resolve_dispute(
-        ctx: Context<ResolveDispute>,
-        consumer_share: u8,
-        provider_share: u8,
-    ) -> Result<()> {
+    // Release funds from escrow to provider. The consumer can release at any
+    // time; anyone else only after release_time, once the match has
+    // completed and the consumer's challenge window has closed.
+    pub fn release_funds(ctx: Context<ReleaseFunds>) -> Result<()> {
+        // Replicated matches only pay out once a quorum agrees with their result
+        check_replication(&ctx.accounts.match_record, ctx.accounts.replication_group.as_ref())?;
+        
         let escrow = &mut ctx.accounts.escrow;
-        let arbitrator = &ctx.accounts.arbitrator;
+        let signer = &ctx.accounts.signer;
+        
+        // Check if release is authorized
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        if escrow.consumer != signer.key() {
+            require!(
+                current_time >= escrow.release_time,
+                TransactionError::UnauthorizedRelease
+            );
+            
+            let match_record = &ctx.accounts.match_record;
+            require!(
+                match_record.status == MatchStatus::Completed
+                    || (match_record.status == MatchStatus::PendingCompletion
+                        && current_time > match_record.challenge_deadline),
+                TransactionError::MatchNotFinished
+            );
+        }
         
         // Check escrow status
         require!(
-            escrow.status == EscrowStatus::Disputed,
+            escrow.status == EscrowStatus::Created,
             TransactionError::InvalidEscrowStatus
         );
         
-        // Validate shares
+        // Milestone escrows are released one milestone at a time
         require!(
-            consumer_share + provider_share == 100,
-            TransactionError::InvalidShares
+            escrow.mode != EscrowMode::Milestone,
+            TransactionError::MilestoneEscrow
         );
         
-        // Update escrow status
-        escrow.status = EscrowStatus::Resolved;
-        escrow.updated_at = Clock::get()?.unix_timestamp;
+        // Funds stay frozen while the match is disputed
+        require!(
+            ctx.accounts.match_record.status != MatchStatus::Disputed,
+            TransactionError::MatchDisputed
+        );
         
-        // Calculate amounts
-        let consumer_amount = (escrow.amount as u128 * consumer_share as u128 / 100) as u64;
-        let provider_amount = escrow.amount - consumer_amount;
+        // Anything already streamed to the provider stays paid
+        let amount = escrow.remaining();
         
-        // Transfer consumer share
-        if consumer_amount > 0 {
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.consumer_token_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
-            };
-            
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let seeds = &[
-                b"escrow".as_ref(),
-                escrow.key().as_ref(),
-                &[*ctx.bumps.get("escrow_authority").unwrap()],
-            ];
-            let signer_seeds = &[&seeds[..]];
-            
-            let cpi_ctx = CpiContext::new_with_signer(
-                cpi_program,
-                cpi_accounts,
-                signer_seeds,
-            );
+        // Update escrow status
+        escrow.status = EscrowStatus::Released;
+        escrow.released_amount = escrow.amount;
+        escrow.updated_at = current_time;
+        
+        // Transfer funds from escrow to provider
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            ctx.accounts.match_record.matcher,
+            amount,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        // The escrow is settled, so return the vault rent to the consumer
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.consumer,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        emit!(EscrowReleased {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            amount,
+            released_by: signer.key(),
+        });
+
+        Ok(())
+    }
+
+    // End a match early (by consumer or provider). The provider is paid for
+    // the time worked since the match started and the consumer gets the rest
+    // back, less a cancellation fee on the unearned part that goes to
+    // whichever party did not initiate. A provider who has not earned enough
+    // to cover the fee pays the rest from their own token account.
+    pub fn terminate_match(ctx: Context<TerminateMatch>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let match_record = &ctx.accounts.match_record;
+        let signer = &ctx.accounts.signer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let by_consumer = escrow.consumer == signer.key();
+        require!(
+            by_consumer || escrow.provider == signer.key(),
+            TransactionError::UnauthorizedTermination
+        );
+        
+        // Check escrow status
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Milestones are settled one by one instead
+        require!(
+            escrow.mode != EscrowMode::Milestone,
+            TransactionError::MilestoneEscrow
+        );
+        
+        require!(
+            match_record.status == MatchStatus::Confirmed
+                || match_record.status == MatchStatus::InProgress,
+            TransactionError::MatchNotActive
+        );
+        
+        // Pro-rate over the scheduled duration by the time actually worked
+        let earned = if match_record.status == MatchStatus::InProgress {
+            let duration = match_record.end_time - match_record.start_time;
+            let worked = (current_time.min(match_record.end_time) - match_record.actual_start)
+                .clamp(0, duration);
+            (escrow.amount as u128 * worked as u128 / duration as u128) as u64
+        } else {
+            0
+        };
+        
+        // Anything already streamed to the provider counts towards what they earned
+        let remaining = escrow.remaining();
+        let mut provider_amount = earned.saturating_sub(escrow.released_amount).min(remaining);
+        let mut consumer_amount = remaining - provider_amount;
+        
+        let cancellation_fee = if by_consumer {
+            let fee = bps_of(consumer_amount, ctx.accounts.fees.config.cancellation_fee_bps);
+            consumer_amount -= fee;
+            provider_amount += fee;
+            fee
+        } else {
+            let fee = bps_of(consumer_amount, ctx.accounts.fees.config.cancellation_fee_bps);
+            let from_earnings = fee.min(provider_amount);
+            provider_amount -= from_earnings;
+            consumer_amount += fee;
             
-            token::transfer(cpi_ctx, consumer_amount)?;
-        }
+            // Top the vault up with whatever the provider's earnings don't cover
+            let shortfall = fee - from_earnings;
+            if shortfall > 0 {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.provider_token_account.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: signer.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+                
+                token::transfer(cpi_ctx, shortfall)?;
+            }
+            fee
+        };
         
-        // Transfer provider share
+        // A replica is only paid for its work once a quorum agrees with its
+        // result, or its group has expired
         if provider_amount > 0 {
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.provider_token_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
-            };
+            check_replication(match_record, ctx.accounts.replication_group.as_ref())?;
+        }
+        
+        // Update escrow status
+        escrow.status = EscrowStatus::Terminated;
+        escrow.released_amount = escrow.amount;
+        escrow.refunded_amount += consumer_amount;
+        escrow.updated_at = current_time;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        refund_consumer(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            consumer_amount,
+            true,
+        )?;
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            match_record.matcher,
+            provider_amount,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        // The escrow is settled, so return the vault rent to the consumer
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.consumer,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        let match_key = match_record.key();
+        let seeds = &[
+            b"match_authority".as_ref(),
+            match_key.as_ref(),
+            &[*ctx.bumps.get("match_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = matching_engine::cpi::accounts::MarkTerminated {
+            match_record: match_record.to_account_info(),
+            match_authority: ctx.accounts.match_authority.to_account_info(),
+            payload_key: ctx.accounts.payload_key.to_account_info(),
+            consumer: ctx.accounts.consumer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.matching_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        
+        matching_engine::cpi::mark_terminated(cpi_ctx, signer.key())?;
+        
+        emit!(MatchTerminatedEarly {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            terminated_by: signer.key(),
+            provider_amount,
+            consumer_amount,
+            cancellation_fee,
+        });
+
+        Ok(())
+    }
+
+    // Refund escrow to consumer. The provider can refund an undisputed
+    // escrow outright. The arbitrator assigned to a disputed escrow refunds
+    // it by ruling fully for the consumer, which can still be appealed.
+    pub fn refund_escrow(ctx: Context<RefundEscrow>) -> Result<()> {
+        let signer = &ctx.accounts.signer;
+        
+        if ctx.accounts.escrow.provider != signer.key() {
+            let escrow = &ctx.accounts.escrow;
+            require!(
+                escrow.status == EscrowStatus::Disputed,
+                TransactionError::InvalidEscrowStatus
+            );
             
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let seeds = &[
-                b"escrow".as_ref(),
-                escrow.key().as_ref(),
-                &[*ctx.bumps.get("escrow_authority").unwrap()],
-            ];
-            let signer_seeds = &[&seeds[..]];
+            // High-value disputes are decided by a panel instead
+            require!(
+                !escrow.panel_dispute,
+                TransactionError::PanelDispute
+            );
             
-            let cpi_ctx = CpiContext::new_with_signer(
-                cpi_program,
-                cpi_accounts,
-                signer_seeds,
+            require!(
+                escrow.arbitrator == signer.key(),
+                TransactionError::UnauthorizedRefund
+            );
+            
+            let arbitrator = ctx
+                .accounts
+                .arbitrator
+                .as_mut()
+                .ok_or(TransactionError::UnauthorizedRefund)?;
+            require!(
+                arbitrator.active && arbitrator.authority == signer.key(),
+                TransactionError::UnauthorizedRefund
             );
+            arbitrator.resolved_count += 1;
             
-            token::transfer(cpi_ctx, provider_amount)?;
+            let dispute = ctx
+                .accounts
+                .dispute
+                .as_mut()
+                .ok_or(TransactionError::MissingDispute)?;
+            return issue_ruling(&mut ctx.accounts.escrow, dispute, signer.key(), 100);
         }
         
-        emit!(DisputeResolved {
+        let escrow = &mut ctx.accounts.escrow;
+        
+        // Check escrow status
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Funds stay frozen while the match is disputed
+        require!(
+            ctx.accounts.match_record.status != MatchStatus::Disputed,
+            TransactionError::MatchDisputed
+        );
+        
+        // Anything already streamed to the provider stays paid
+        let amount = escrow.remaining();
+        
+        // Update escrow status
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refunded_amount += amount;
+        escrow.updated_at = Clock::get()?.unix_timestamp;
+        
+        // Transfer funds from escrow back to consumer
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        refund_consumer(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            amount,
+            true,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        // The escrow is settled, so return the vault rent to the consumer
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.consumer,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        emit!(EscrowRefunded {
             escrow_id: escrow.key(),
             match_id: escrow.match_id,
-            resolved_by: arbitrator.key(),
-            consumer_share,
-            provider_share,
+            amount,
+            refunded_by: signer.key(),
+        });
+
+        Ok(())
+    }
+
+    // Reclaim the escrow (by consumer) when the provider has not started the
+    // match within the grace period after its scheduled start. The match is
+    // closed as a no-show and the provider loses reputation.
+    pub fn claim_no_show(ctx: Context<ClaimNoShow>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let match_record = &ctx.accounts.match_record;
+        let consumer = &ctx.accounts.consumer;
+        
+        require!(
+            escrow.consumer == consumer.key(),
+            TransactionError::ConsumerMismatch
+        );
+        
+        // Check escrow status
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // A match that was started, completed or disputed has moved on
+        require!(
+            match_record.status == MatchStatus::Confirmed,
+            TransactionError::MatchAlreadyStarted
+        );
+        
+        require!(
+            Clock::get()?.unix_timestamp > match_record.start_time + NO_SHOW_GRACE_PERIOD,
+            TransactionError::NoShowGracePeriod
+        );
+        
+        let amount = escrow.remaining();
+        
+        // Update escrow status
+        escrow.status = EscrowStatus::Refunded;
+        escrow.released_amount = escrow.amount;
+        escrow.refunded_amount += amount;
+        escrow.updated_at = Clock::get()?.unix_timestamp;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        refund_consumer(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            amount,
+            true,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &consumer.to_account_info(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        // Close the match as a no-show
+        let match_key = match_record.key();
+        let seeds = &[
+            b"match_authority".as_ref(),
+            match_key.as_ref(),
+            &[*ctx.bumps.get("match_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = matching_engine::cpi::accounts::MarkNoShow {
+            match_record: match_record.to_account_info(),
+            match_authority: ctx.accounts.match_authority.to_account_info(),
+            payload_key: ctx.accounts.payload_key.to_account_info(),
+            consumer: consumer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.matching_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        
+        matching_engine::cpi::mark_no_show(cpi_ctx)?;
+        
+        // Penalize the provider's reputation
+        let seeds = &[
+            b"reputation_authority".as_ref(),
+            &[*ctx.bumps.get("reputation_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = resource_manager::cpi::accounts::RecordNoShow {
+            profile: ctx.accounts.provider_profile.to_account_info(),
+            reputation_authority: ctx.accounts.reputation_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.resource_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        
+        resource_manager::cpi::record_no_show(cpi_ctx)?;
+        
+        emit!(NoShowClaimed {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            provider: escrow.provider,
+            amount,
         });
 
         Ok(())
     }
+
+    // Withdraw the payment accrued so far on a streaming escrow (by provider)
+    pub fn withdraw_accrued(ctx: Context<WithdrawAccrued>) -> Result<()> {
+        // Replicated matches only pay out once a quorum agrees with their result
+        check_replication(&ctx.accounts.match_record, ctx.accounts.replication_group.as_ref())?;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        let provider = &ctx.accounts.provider;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.provider == provider.key(),
+            TransactionError::UnauthorizedWithdrawal
+        );
+        
+        require!(
+            escrow.mode == EscrowMode::Streaming,
+            TransactionError::NotStreamingEscrow
+        );
+        
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Funds stay frozen while the match is disputed
+        require!(
+            ctx.accounts.match_record.status != MatchStatus::Disputed,
+            TransactionError::MatchDisputed
+        );
+        
+        // Nothing can be withdrawn before the provider has started work
+        let match_status = ctx.accounts.match_record.status;
+        require!(
+            match_status == MatchStatus::InProgress
+                || match_status == MatchStatus::PendingCompletion
+                || match_status == MatchStatus::Completed,
+            TransactionError::MatchNotStarted
+        );
+        
+        // Extending a stream re-spreads it, so the accrual can briefly trail
+        // what was already withdrawn
+        let amount = escrow
+            .accrued(current_time, ctx.accounts.match_record.actual_start)
+            .saturating_sub(escrow.released_amount);
+        require!(
+            amount > 0,
+            TransactionError::NothingAccrued
+        );
+        
+        escrow.released_amount += amount;
+        escrow.updated_at = current_time;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            ctx.accounts.match_record.matcher,
+            amount,
+        )?;
+        
+        // The whole stream has been paid out
+        if escrow.remaining() == 0 {
+            escrow.status = EscrowStatus::Released;
+            
+            refund_pending_extension(
+                escrow,
+                ctx.accounts.extension.as_ref(),
+                &ctx.accounts.escrow_token_account,
+                ctx.accounts.consumer_token_account.as_ref(),
+                &ctx.accounts.escrow_authority,
+                &ctx.accounts.token_program,
+                signer_seeds,
+            )?;
+            
+            close_vault(
+                &ctx.accounts.escrow_token_account,
+                ctx.accounts.consumer_token_account.as_ref(),
+                &ctx.accounts.consumer,
+                &ctx.accounts.escrow_authority,
+                &ctx.accounts.token_program,
+                signer_seeds,
+            )?;
+        }
+        
+        emit!(AccruedWithdrawn {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            amount,
+            released_amount: escrow.released_amount,
+        });
+
+        Ok(())
+    }
+
+    // Stop a streaming escrow early (by consumer). The provider is paid what
+    // has accrued and the unaccrued portion is refunded. A match that is
+    // still running is marked terminated.
+    pub fn stop_stream(ctx: Context<StopStream>) -> Result<()> {
+        // Replicated matches only pay out once a quorum agrees with their result
+        check_replication(&ctx.accounts.match_record, ctx.accounts.replication_group.as_ref())?;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        let consumer = &ctx.accounts.consumer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.consumer == consumer.key(),
+            TransactionError::UnauthorizedStreamStop
+        );
+        
+        require!(
+            escrow.mode == EscrowMode::Streaming,
+            TransactionError::NotStreamingEscrow
+        );
+        
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Funds stay frozen while the match is disputed
+        require!(
+            ctx.accounts.match_record.status != MatchStatus::Disputed,
+            TransactionError::MatchDisputed
+        );
+        
+        let accrued = escrow.accrued(current_time, ctx.accounts.match_record.actual_start);
+        let provider_amount = accrued.saturating_sub(escrow.released_amount);
+        let consumer_amount = escrow.remaining() - provider_amount;
+        
+        escrow.status = EscrowStatus::Released;
+        escrow.released_amount = escrow.amount;
+        escrow.refunded_amount += consumer_amount;
+        escrow.updated_at = current_time;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            ctx.accounts.match_record.matcher,
+            provider_amount,
+        )?;
+        
+        refund_consumer(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            consumer_amount,
+            true,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &consumer.to_account_info(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        // A match still running ends here, which also drops its payload key
+        let match_status = ctx.accounts.match_record.status;
+        if match_status == MatchStatus::Confirmed || match_status == MatchStatus::InProgress {
+            let match_key = ctx.accounts.match_record.key();
+            let seeds = &[
+                b"match_authority".as_ref(),
+                match_key.as_ref(),
+                &[*ctx.bumps.get("match_authority").unwrap()],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = matching_engine::cpi::accounts::MarkTerminated {
+                match_record: ctx.accounts.match_record.to_account_info(),
+                match_authority: ctx.accounts.match_authority.to_account_info(),
+                payload_key: ctx.accounts.payload_key.to_account_info(),
+                consumer: consumer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.matching_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            
+            matching_engine::cpi::mark_terminated(cpi_ctx, consumer.key())?;
+        }
+        
+        emit!(StreamStopped {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            provider_amount,
+            consumer_amount,
+        });
+
+        Ok(())
+    }
+
+    // Release a single milestone to the provider. The consumer can release
+    // at any time; anyone can release once the milestone deadline has passed.
+    pub fn release_milestone(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        // Replicated matches only pay out once a quorum agrees with their result
+        check_replication(&ctx.accounts.match_record, ctx.accounts.replication_group.as_ref())?;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        let signer = &ctx.accounts.signer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.mode == EscrowMode::Milestone,
+            TransactionError::NotMilestoneEscrow
+        );
+        
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Funds stay frozen while the match is disputed
+        require!(
+            ctx.accounts.match_record.status != MatchStatus::Disputed,
+            TransactionError::MatchDisputed
+        );
+        
+        let consumer_key = escrow.consumer;
+        let milestone = escrow
+            .milestones
+            .get_mut(index as usize)
+            .ok_or(TransactionError::InvalidMilestoneIndex)?;
+        
+        require!(
+            milestone.status == MilestoneStatus::Pending,
+            TransactionError::InvalidMilestoneStatus
+        );
+        
+        if current_time < milestone.deadline {
+            require!(
+                consumer_key == signer.key(),
+                TransactionError::UnauthorizedRelease
+            );
+        }
+        
+        milestone.status = MilestoneStatus::Released;
+        let amount = milestone.amount;
+        
+        escrow.released_amount += amount;
+        escrow.updated_at = current_time;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            ctx.accounts.match_record.matcher,
+            amount,
+        )?;
+        
+        if escrow.milestones_settled() {
+            escrow.status = EscrowStatus::Released;
+            
+            close_vault(
+                &ctx.accounts.escrow_token_account,
+                ctx.accounts.consumer_token_account.as_ref(),
+                &ctx.accounts.consumer,
+                &ctx.accounts.escrow_authority,
+                &ctx.accounts.token_program,
+                signer_seeds,
+            )?;
+        }
+        
+        emit!(MilestoneReleased {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            index,
+            amount,
+            released_by: signer.key(),
+        });
+
+        Ok(())
+    }
+
+    // Dispute a single milestone (by consumer or provider). remaining_accounts
+    // holds candidate Arbitrator accounts in draw order when the escrow has
+    // no arbitrator yet.
+    pub fn dispute_milestone<'info>(
+        ctx: Context<'_, '_, '_, 'info, DisputeMilestone<'info>>,
+        index: u8,
+        reason_code: DisputeReason,
+        reason: String,
+    ) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let dispute = &mut ctx.accounts.dispute;
+        let signer = &ctx.accounts.signer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.consumer == signer.key() || escrow.provider == signer.key(),
+            TransactionError::UnauthorizedDispute
+        );
+        
+        require!(
+            reason.len() <= MAX_DISPUTE_REASON_LEN,
+            TransactionError::DisputeReasonTooLong
+        );
+        
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        let milestone = escrow
+            .milestones
+            .get_mut(index as usize)
+            .ok_or(TransactionError::InvalidMilestoneIndex)?;
+        
+        require!(
+            milestone.status == MilestoneStatus::Pending,
+            TransactionError::InvalidMilestoneStatus
+        );
+        
+        milestone.status = MilestoneStatus::Disputed;
+        assign_arbitrator(escrow, ctx.remaining_accounts, ctx.accounts.config.arbitrator_count)?;
+        escrow.updated_at = current_time;
+        
+        // Keep a persistent record for the arbitrator, as for escrow disputes
+        dispute.escrow = escrow.key();
+        dispute.claimant = signer.key();
+        dispute.reason_code = reason_code;
+        dispute.reason = reason.clone();
+        dispute.evidence = Vec::new();
+        dispute.evidence_deadline = current_time + EVIDENCE_PERIOD;
+        dispute.resolution_deadline = dispute.evidence_deadline + ARBITRATION_PERIOD;
+        dispute.milestone = Some(index);
+        dispute.bump = *ctx.bumps.get("dispute").unwrap();
+        dispute.created_at = current_time;
+        
+        emit!(MilestoneDisputed {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            index,
+            disputed_by: signer.key(),
+            arbitrator: escrow.arbitrator,
+            reason_code,
+            reason,
+            evidence_deadline: dispute.evidence_deadline,
+            resolution_deadline: dispute.resolution_deadline,
+        });
+
+        Ok(())
+    }
+
+    // Resolve a disputed milestone by splitting its amount, once the
+    // evidence period has closed
+    pub fn resolve_milestone_dispute(
+        ctx: Context<ResolveMilestoneDispute>,
+        index: u8,
+        consumer_share: u8,
+        provider_share: u8,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.settlement.dispute.evidence_deadline,
+            TransactionError::EvidencePeriodOpen
+        );
+        
+        // Validate shares
+        require!(
+            consumer_share as u16 + provider_share as u16 == 100,
+            TransactionError::InvalidShares
+        );
+        
+        settle_milestone_dispute(
+            &mut ctx.accounts.settlement,
+            *ctx.bumps.get("escrow_authority").unwrap(),
+            index,
+            consumer_share,
+            ctx.accounts.arbitrator.key(),
+        )?;
+        
+        ctx.accounts.arbitrator_record.resolved_count += 1;
+
+        Ok(())
+    }
+
+    // Apply the configured default split to a milestone dispute the
+    // arbitrator did not decide by its resolution deadline. Anyone can call
+    // this; the arbitrator is recorded as having missed the dispute.
+    pub fn resolve_milestone_dispute_by_default(
+        ctx: Context<ResolveMilestoneDisputeByDefault>,
+        index: u8,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.settlement.dispute.resolution_deadline,
+            TransactionError::DisputeDeadlineNotReached
+        );
+        
+        let arbitrator = &mut ctx.accounts.arbitrator_record;
+        arbitrator.missed_count += 1;
+        
+        emit!(ArbitratorMissedDeadline {
+            authority: arbitrator.authority,
+            escrow_id: ctx.accounts.settlement.escrow.key(),
+        });
+        
+        let consumer_share = ctx.accounts.settlement.fees.config.default_consumer_share;
+        settle_milestone_dispute(
+            &mut ctx.accounts.settlement,
+            *ctx.bumps.get("escrow_authority").unwrap(),
+            index,
+            consumer_share,
+            ctx.accounts.caller.key(),
+        )
+    }
+
+    // Settle an escrow for the hours actually used (by provider). The hours
+    // must be co-signed by the consumer or attested by the usage oracle; an
+    // oracle attestation has to wait for the challenge window to close. The
+    // provider is paid for at least the minimum rental time recorded on the
+    // match, and for no more than its job spec's wall-clock limit; the rest
+    // of the escrow is refunded to the consumer.
+    pub fn settle_usage(ctx: Context<SettleUsage>, hours_used: u64) -> Result<()> {
+        // Replicated matches only pay out once a quorum agrees with their result
+        check_replication(&ctx.accounts.match_record, ctx.accounts.replication_group.as_ref())?;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        let match_record = &ctx.accounts.match_record;
+        let provider = &ctx.accounts.provider;
+        let attestor = &ctx.accounts.attestor;
+        
+        require!(
+            escrow.provider == provider.key(),
+            TransactionError::UnauthorizedSettlement
+        );
+        
+        require!(
+            attestor.key() == escrow.consumer || attestor.key() == ctx.accounts.config.usage_oracle,
+            TransactionError::InvalidUsageAttestation
+        );
+        
+        require!(
+            escrow.mode == EscrowMode::Lump,
+            TransactionError::InvalidEscrowMode
+        );
+        
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Usage can only be settled once the job has finished
+        require!(
+            match_record.status == MatchStatus::PendingCompletion ||
+            match_record.status == MatchStatus::Completed,
+            TransactionError::MatchNotFinished
+        );
+        
+        // An oracle attestation cannot cut the consumer's challenge window short
+        if match_record.status == MatchStatus::PendingCompletion && attestor.key() != escrow.consumer {
+            require!(
+                Clock::get()?.unix_timestamp > match_record.challenge_deadline,
+                TransactionError::ChallengeWindowOpen
+            );
+        }
+        
+        // Never bill past the job spec's wall-clock limit, but bill at least
+        // the minimum rental in force when the match was made
+        let max_hours = (match_record.max_wall_clock_secs + 3599) / 3600;
+        let billable_hours = hours_used
+            .min(max_hours)
+            .max(match_record.min_rental_hours as u64);
+        let provider_amount = match_record
+            .price_per_hour
+            .checked_mul(billable_hours)
+            .ok_or(TransactionError::InvalidAmount)?
+            .min(escrow.remaining());
+        let consumer_amount = escrow.remaining() - provider_amount;
+        
+        escrow.status = EscrowStatus::Released;
+        escrow.released_amount = escrow.amount;
+        escrow.refunded_amount += consumer_amount;
+        escrow.updated_at = Clock::get()?.unix_timestamp;
+        
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.key().as_ref(),
+            &[*ctx.bumps.get("escrow_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        pay_provider(
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            &ctx.accounts.fees,
+            ctx.accounts.match_record.matcher,
+            provider_amount,
+        )?;
+        
+        refund_consumer(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+            consumer_amount,
+            true,
+        )?;
+        
+        refund_pending_extension(
+            escrow,
+            ctx.accounts.extension.as_ref(),
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        close_vault(
+            &ctx.accounts.escrow_token_account,
+            ctx.accounts.consumer_token_account.as_ref(),
+            &ctx.accounts.consumer,
+            &ctx.accounts.escrow_authority,
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+        
+        emit!(UsageSettled {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            hours_used,
+            provider_amount,
+            consumer_amount,
+            attested_by: attestor.key(),
+        });
+
+        Ok(())
+    }
+
+    // Dispute an escrow transaction. Unless the dispute goes to a panel,
+    // remaining_accounts holds candidate Arbitrator accounts in draw order
+    // for assigning the escrow's arbitrator.
+    pub fn dispute_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, DisputeEscrow<'info>>,
+        reason_code: DisputeReason,
+        reason: String,
+    ) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let dispute = &mut ctx.accounts.dispute;
+        let signer = &ctx.accounts.signer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        // Either consumer or provider can dispute
+        require!(
+            escrow.consumer == signer.key() || escrow.provider == signer.key(),
+            TransactionError::UnauthorizedDispute
+        );
+        
+        require!(
+            reason.len() <= MAX_DISPUTE_REASON_LEN,
+            TransactionError::DisputeReasonTooLong
+        );
+        
+        // Check escrow status
+        require!(
+            escrow.status == EscrowStatus::Created,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        let panel_threshold = ctx.accounts.allowed_mint.panel_threshold;
+        if panel_threshold > 0 && escrow.remaining() >= panel_threshold {
+            // High-value disputes go to a randomly drawn panel, seeded by
+            // the hash of a slot that has not been produced yet
+            escrow.panel_dispute = true;
+            dispute.draw_slot = Clock::get()?.slot + PANEL_DRAW_DELAY_SLOTS;
+        } else {
+            assign_arbitrator(escrow, ctx.remaining_accounts, ctx.accounts.config.arbitrator_count)?;
+        }
+        
+        // Keep the match in agreement. A replica outvoted by its replication
+        // group stays disputable after its challenge window closes.
+        let match_key = ctx.accounts.match_record.key();
+        let seeds = &[
+            b"match_authority".as_ref(),
+            match_key.as_ref(),
+            &[*ctx.bumps.get("match_authority").unwrap()],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = matching_engine::cpi::accounts::DisputeMatch {
+            match_record: ctx.accounts.match_record.to_account_info(),
+            match_authority: ctx.accounts.match_authority.to_account_info(),
+            payload_key: ctx.accounts.payload_key.to_account_info(),
+            consumer: ctx.accounts.consumer.to_account_info(),
+            replication_group: ctx
+                .accounts
+                .replication_group
+                .as_ref()
+                .map(|group| group.to_account_info()),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.matching_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        
+        matching_engine::cpi::dispute_match(cpi_ctx, signer.key(), reason.clone())?;
+        
+        // Update escrow status
+        escrow.status = EscrowStatus::Disputed;
+        escrow.updated_at = current_time;
+        
+        // Keep a persistent record for the arbitrators
+        dispute.escrow = escrow.key();
+        dispute.claimant = signer.key();
+        dispute.reason_code = reason_code;
+        dispute.reason = reason.clone();
+        dispute.evidence = Vec::new();
+        dispute.evidence_deadline = current_time + EVIDENCE_PERIOD;
+        dispute.resolution_deadline = dispute.evidence_deadline + ARBITRATION_PERIOD;
+        dispute.bump = *ctx.bumps.get("dispute").unwrap();
+        dispute.created_at = current_time;
+        
+        emit!(EscrowDisputed {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            disputed_by: signer.key(),
+            arbitrator: escrow.arbitrator,
+            reason_code,
+            reason,
+            evidence_deadline: dispute.evidence_deadline,
+            resolution_deadline: dispute.resolution_deadline,
+        });
+
+        Ok(())
+    }
+
+    // Attach evidence to a dispute (by the consumer or provider) until the
+    // evidence deadline
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let escrow = &ctx.accounts.escrow;
+        let submitter = &ctx.accounts.submitter;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.consumer == submitter.key() || escrow.provider == submitter.key(),
+            TransactionError::UnauthorizedEvidence
+        );
+        
+        require!(
+            current_time <= dispute.evidence_deadline,
+            TransactionError::EvidencePeriodClosed
+        );
+        
+        require!(
+            uri.len() <= MAX_EVIDENCE_URI_LEN,
+            TransactionError::EvidenceUriTooLong
+        );
+        
+        // Each party has its own share of the evidence slots
+        let submitted = dispute
+            .evidence
+            .iter()
+            .filter(|e| e.submitter == submitter.key())
+            .count();
+        require!(
+            submitted < MAX_EVIDENCE_PER_PARTY,
+            TransactionError::TooMuchEvidence
+        );
+        
+        dispute.evidence.push(Evidence {
+            content_hash,
+            uri: uri.clone(),
+            submitter: submitter.key(),
+            submitted_at: current_time,
+        });
+        
+        emit!(EvidenceSubmitted {
+            escrow_id: escrow.key(),
+            submitter: submitter.key(),
+            content_hash,
+            uri,
+        });
+
+        Ok(())
+    }
+
+    // Rule on a disputed escrow. The split is applied by execute_ruling once
+    // the appeal window has passed without an appeal.
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        consumer_share: u8,
+        provider_share: u8,
+    ) -> Result<()> {
+        let arbitrator = &ctx.accounts.arbitrator;
+        let escrow = &ctx.accounts.escrow;
+        
+        // Check escrow status
+        require!(
+            escrow.status == EscrowStatus::Disputed,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // High-value disputes are decided by a panel instead
+        require!(
+            !escrow.panel_dispute,
+            TransactionError::PanelDispute
+        );
+        
+        require!(
+            escrow.arbitrator == arbitrator.key(),
+            TransactionError::ArbitratorMismatch
+        );
+        
+        // Validate shares
+        require!(
+            consumer_share as u16 + provider_share as u16 == 100,
+            TransactionError::InvalidShares
+        );
+        
+        issue_ruling(
+            &mut ctx.accounts.escrow,
+            &mut ctx.accounts.dispute,
+            arbitrator.key(),
+            consumer_share,
+        )?;
+        
+        ctx.accounts.arbitrator_record.resolved_count += 1;
+
+        Ok(())
+    }
+
+    // Apply a ruling whose appeal window has passed. Anyone can call this.
+    pub fn execute_ruling(ctx: Context<ExecuteRuling>) -> Result<()> {
+        let escrow = &ctx.accounts.settlement.escrow;
+        let dispute = &ctx.accounts.settlement.dispute;
+        
+        require!(
+            escrow.status == EscrowStatus::Ruled,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        require!(
+            Clock::get()?.unix_timestamp > dispute.appeal_deadline,
+            TransactionError::AppealWindowOpen
+        );
+        
+        let consumer_share = dispute.ruling_consumer_share;
+        let ruled_by = dispute.ruled_by;
+        
+        settle_dispute(
+            &mut ctx.accounts.settlement,
+            *ctx.bumps.get("escrow_authority").unwrap(),
+            *ctx.bumps.get("match_authority").unwrap(),
+            consumer_share,
+            100 - consumer_share,
+            false,
+        )?;
+        
+        let escrow = &ctx.accounts.settlement.escrow;
+        emit!(DisputeResolved {
+            escrow_id: escrow.key(),
+            match_id: escrow.match_id,
+            resolved_by: ruled_by,
+            consumer_share,
+            provider_share: 100 - consumer_share,
+        });
+
+        Ok(())
+    }
+
+    // Appeal a ruling (by the losing party) within the appeal window. The
+    // appellant posts a bond into the escrow vault and the dispute goes to a
+    // larger panel whose decision is final. The bond is returned if the
+    // outcome moves in the appellant's favour and forfeited otherwise.
+    pub fn appeal_ruling(ctx: Context<AppealRuling>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let dispute = &mut ctx.accounts.dispute;
+        let appellant = &ctx.accounts.appellant;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.status == EscrowStatus::Ruled,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        require!(
+            current_time <= dispute.appeal_deadline,
+            TransactionError::AppealWindowClosed
+        );
+        
+        // Only one appeal per dispute
+        require!(
+            dispute.round == 0,
+            TransactionError::AppealAlreadyFiled
+        );
+        
+        let losing = if appellant.key() == escrow.consumer {
+            dispute.ruling_consumer_share <= 50
+        } else if appellant.key() == escrow.provider {
+            dispute.ruling_consumer_share >= 50
+        } else {
+            false
+        };
+        require!(
+            losing,
+            TransactionError::UnauthorizedAppeal
+        );
+        
+        let bond = bps_of(escrow.remaining(), APPEAL_BOND_BPS);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.appellant_token_account.to_account_info(),
+            to: ctx.accounts.escrow_token_account.to_account_info(),
+            authority: appellant.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token::transfer(cpi_ctx, bond)?;
+        
+        dispute.round = 1;
+        dispute.draw_slot = Clock::get()?.slot + PANEL_DRAW_DELAY_SLOTS;
+        dispute.appellant = appellant.key();
+        dispute.appeal_bond = bond;
+        dispute.evidence_deadline = current_time + EVIDENCE_PERIOD;
+        dispute.resolution_deadline = dispute.evidence_deadline + ARBITRATION_PERIOD;
+        
+        escrow.panel_dispute = true;
+        escrow.status = EscrowStatus::Disputed;
+        escrow.updated_at = current_time;
+        
+        emit!(RulingAppealed {
+            escrow_id: escrow.key(),
+            appellant: appellant.key(),
+            appeal_bond: bond,
+            resolution_deadline: dispute.resolution_deadline,
+        });
+
+        Ok(())
+    }
+
+    // Configure arbitrator staking (by admin, once). This also creates the
+    // insurance vault that slashed stake is paid into.
+    pub fn initialize_arbitration(
+        ctx: Context<InitializeArbitration>,
+        min_arbitrator_stake: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        require!(
+            config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        require!(
+            config.stake_mint == Pubkey::default(),
+            TransactionError::ArbitrationAlreadyInitialized
+        );
+        
+        config.stake_mint = ctx.accounts.stake_mint.key();
+        config.min_arbitrator_stake = min_arbitrator_stake;
+
+        Ok(())
+    }
+
+    // Set the escrow value from which disputes go to a panel (by admin).
+    // Zero disables panels for the mint.
+    pub fn set_panel_threshold(ctx: Context<SetPanelThreshold>, panel_threshold: u64) -> Result<()> {
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        allowed_mint.panel_threshold = panel_threshold;
+
+        Ok(())
+    }
+
+    // Set the most a single insurance claim can pay out in a mint (by admin).
+    // Zero disables claims for the mint.
+    pub fn set_coverage_limit(ctx: Context<SetCoverageLimit>, coverage_limit: u64) -> Result<()> {
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            TransactionError::UnauthorizedAdmin
+        );
+        
+        allowed_mint.coverage_limit = coverage_limit;
+        
+        emit!(CoverageLimitUpdated {
+            mint: allowed_mint.mint,
+            coverage_limit,
+        });
+
+        Ok(())
+    }
+
+    // Claim compensation from the insurance fund for a failed job (by
+    // consumer). Only failures the consumer cannot declare on their own
+    // qualify: a dispute ruled at least partly for the consumer, a replica
+    // whose result the quorum rejected, or a replication group that expired
+    // without a quorum. The escrow must be settled, and the claim never
+    // covers more than the consumer paid out of it. Every claim waits for the
+    // escrow's arbitrator, who is drawn here if the escrow was never disputed.
+    pub fn file_insurance_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, FileInsuranceClaim<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let claim = &mut ctx.accounts.claim;
+        let consumer = &ctx.accounts.consumer;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.consumer == consumer.key(),
+            TransactionError::ConsumerMismatch
+        );
+        
+        // What the consumer got back is only final once the escrow is settled
+        require!(
+            escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Disputed,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // A ruling only counts if it gave the consumer something back
+        let ruled = match ctx.accounts.dispute.as_ref() {
+            Some(dispute) => {
+                let resolved = match dispute.milestone {
+                    Some(index) => escrow
+                        .milestones
+                        .get(index as usize)
+                        .map_or(false, |m| m.status == MilestoneStatus::Resolved),
+                    None => escrow.status == EscrowStatus::Resolved,
+                };
+                resolved && dispute.ruling_consumer_share > 0
+            }
+            None => false,
+        };
+        let replica_rejected = match ctx.accounts.replication_group.as_ref() {
+            Some(group) => {
+                group.failed
+                    || (group.finalized
+                        && group.consensus_hash != ctx.accounts.match_record.result_hash)
+            }
+            None => false,
+        };
+        require!(
+            ruled || replica_rejected,
+            TransactionError::ClaimNotEligible
+        );
+        
+        // Cover at most the mint's limit and never more than the consumer lost
+        require!(
+            amount > 0
+                && amount <= ctx.accounts.allowed_mint.coverage_limit
+                && amount <= escrow.amount.saturating_sub(escrow.refunded_amount),
+            TransactionError::ExceedsCoverage
+        );
+        
+        assign_arbitrator(escrow, ctx.remaining_accounts, ctx.accounts.config.arbitrator_count)?;
+        
+        claim.escrow = escrow.key();
+        claim.consumer = consumer.key();
+        claim.mint = escrow.mint;
+        claim.amount = amount;
+        claim.status = ClaimStatus::Pending;
+        claim.reviewed_by = Pubkey::default();
+        claim.bump = *ctx.bumps.get("claim").unwrap();
+        claim.created_at = current_time;
+        claim.updated_at = current_time;
+        
+        emit!(InsuranceClaimFiled {
+            claim_id: claim.key(),
+            escrow_id: escrow.key(),
+            consumer: consumer.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // Approve or reject a pending insurance claim (by the escrow's arbitrator,
+    // or a member of its panel when the dispute went to a panel)
+    pub fn review_insurance_claim(ctx: Context<ReviewInsuranceClaim>, approve: bool) -> Result<()> {
+        let claim = &mut ctx.accounts.claim;
+        let escrow = &ctx.accounts.escrow;
+        let arbitrator = &ctx.accounts.arbitrator;
+        
+        require!(
+            claim.status == ClaimStatus::Pending,
+            TransactionError::InvalidClaimStatus
+        );
+        
+        require!(
+            arbitrator.key() != escrow.consumer && arbitrator.key() != escrow.provider,
+            TransactionError::ConflictedArbitrator
+        );
+        
+        if escrow.panel_dispute {
+            let panel = ctx
+                .accounts
+                .panel
+                .as_ref()
+                .ok_or(TransactionError::NotPanelMember)?;
+            require!(
+                panel.members.iter().any(|m| m.arbitrator == arbitrator.key()),
+                TransactionError::NotPanelMember
+            );
+        } else {
+            require!(
+                escrow.arbitrator == arbitrator.key(),
+                TransactionError::ArbitratorMismatch
+            );
+        }
+        
+        claim.reviewed_by = arbitrator.key();
+        claim.updated_at = Clock::get()?.unix_timestamp;
+        
+        if approve {
+            let config = &ctx.accounts.config;
+            let seeds = &[b"config".as_ref(), &[config.bump]];
+            let signer_seeds = &[&seeds[..]];
+            
+            transfer_from_vault(
+                &ctx.accounts.insurance_fund,
+                &ctx.accounts.consumer_token_account,
+                &config.to_account_info(),
+                &ctx.accounts.token_program,
+                signer_seeds,
+                claim.amount,
+            )?;
+            
+            claim.status = ClaimStatus::Paid;
+        } else {
+            claim.status = ClaimStatus::Rejected;
+        }
+        
+        emit!(InsuranceClaimReviewed {
+            claim_id: claim.key(),
+            reviewed_by: arbitrator.key(),
+            approved: approve,
+            amount: if approve { claim.amount } else { 0 },
+        });
+
+        Ok(())
+    }
+
+    // Add to an arbitrator's stake (by the arbitrator)
+    pub fn stake_arbitrator(ctx: Context<StakeArbitrator>, amount: u64) -> Result<()> {
+        let arbitrator = &mut ctx.accounts.arbitrator;
+        
+        require!(
+            amount > 0,
+            TransactionError::InvalidAmount
+        );
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token::transfer(cpi_ctx, amount)?;
+        
+        arbitrator.stake += amount;
+        
+        emit!(ArbitratorStakeUpdated {
+            authority: arbitrator.authority,
+            stake: arbitrator.stake,
+        });
+
+        Ok(())
+    }
+
+    // Withdraw stake (by the arbitrator) while not sitting on any panel
+    pub fn unstake_arbitrator(ctx: Context<UnstakeArbitrator>, amount: u64) -> Result<()> {
+        let arbitrator = &mut ctx.accounts.arbitrator;
+        let config = &ctx.accounts.config;
+        
+        require!(
+            arbitrator.active_panels == 0,
+            TransactionError::ArbitratorOnPanel
+        );
+        
+        require!(
+            amount > 0 && amount <= arbitrator.stake,
+            TransactionError::InvalidAmount
+        );
+        
+        arbitrator.stake -= amount;
+        
+        let seeds = &[b"config".as_ref(), &[config.bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        transfer_from_vault(
+            &ctx.accounts.stake_vault,
+            &ctx.accounts.authority_token_account,
+            &config.to_account_info(),
+            &ctx.accounts.token_program,
+            signer_seeds,
+            amount,
+        )?;
+        
+        emit!(ArbitratorStakeUpdated {
+            authority: arbitrator.authority,
+            stake: arbitrator.stake,
+        });
+
+        Ok(())
+    }
+
+    // Draw a panel of staked arbitrators for a high-value dispute. Anyone can
+    // call this. remaining_accounts holds the candidate Arbitrator accounts
+    // (writable) in draw order; each must sit at the index drawn for its
+    // attempt, and ineligible candidates are skipped.
+    pub fn draw_panel<'info>(ctx: Context<'_, '_, '_, 'info, DrawPanel<'info>>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let config = &ctx.accounts.config;
+        let round = ctx.accounts.dispute.round;
+        let panel = &mut ctx.accounts.panel;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.status == EscrowStatus::Disputed && escrow.panel_dispute,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // Appeals go to a larger panel
+        let panel_size = if round == 0 { PANEL_SIZE } else { APPEAL_PANEL_SIZE };
+        
+        require!(
+            config.arbitrator_count as usize >= panel_size,
+            TransactionError::PanelIncomplete
+        );
+        
+        // The seed mixes the hash of the slot fixed when the dispute or
+        // appeal was filed with the escrow, so the draw could not be known
+        // then and cannot be retried for a different panel now
+        let seed = panel_seed(
+            &ctx.accounts.slot_hashes,
+            ctx.accounts.dispute.draw_slot,
+            &escrow.key(),
+            round,
+        )?;
+        
+        let mut members: Vec<PanelMember> = Vec::with_capacity(panel_size);
+        for (attempt, info) in ctx.remaining_accounts.iter().enumerate() {
+            require!(
+                attempt < MAX_DRAW_ATTEMPTS,
+                TransactionError::PanelIncomplete
+            );
+            
+            let mut candidate: Account<Arbitrator> = Account::try_from(info)?;
+            require!(
+                candidate.index == draw_index(&seed, attempt as u64, config.arbitrator_count),
+                TransactionError::PanelDrawMismatch
+            );
+            
+            let eligible = candidate.active
+                && candidate.stake >= config.min_arbitrator_stake
+                && candidate.authority != escrow.consumer
+                && candidate.authority != escrow.provider
+                && !members.iter().any(|m| m.arbitrator == candidate.authority);
+            if !eligible {
+                continue;
+            }
+            
+            candidate.active_panels += 1;
+            candidate.exit(ctx.program_id)?;
+            
+            members.push(PanelMember {
+                arbitrator: candidate.authority,
+                consumer_share: 0,
+                voted: false,
+            });
+            if members.len() == panel_size {
+                break;
+            }
+        }
+        
+        require!(
+            members.len() == panel_size,
+            TransactionError::PanelIncomplete
+        );
+        
+        panel.escrow = escrow.key();
+        panel.round = round;
+        panel.members = members;
+        panel.voting_deadline =
+            current_time.max(ctx.accounts.dispute.evidence_deadline) + PANEL_VOTING_PERIOD;
+        panel.finalized = false;
+        panel.bump = *ctx.bumps.get("panel").unwrap();
+        panel.created_at = current_time;
+        
+        emit!(PanelDrawn {
+            escrow_id: escrow.key(),
+            members: panel.members.iter().map(|m| m.arbitrator).collect(),
+            voting_deadline: panel.voting_deadline,
+        });
+
+        Ok(())
+    }
+
+    // Move a panel draw to a new future slot once the scheduled slot's hash
+    // has aged out of the SlotHashes sysvar without a panel being drawn.
+    // Anyone can call this.
+    pub fn reschedule_panel_draw(ctx: Context<ReschedulePanelDraw>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let dispute = &mut ctx.accounts.dispute;
+        let current_slot = Clock::get()?.slot;
+        
+        require!(
+            escrow.status == EscrowStatus::Disputed && escrow.panel_dispute,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        // A panel for this round has already been drawn
+        require!(
+            ctx.accounts.panel.data_is_empty(),
+            TransactionError::PanelAlreadyDrawn
+        );
+        
+        require!(
+            current_slot > dispute.draw_slot + slot_hashes::MAX_ENTRIES as u64,
+            TransactionError::PanelDrawPending
+        );
+        
+        dispute.draw_slot = current_slot + PANEL_DRAW_DELAY_SLOTS;
+        
+        emit!(PanelDrawRescheduled {
+            escrow_id: escrow.key(),
+            round: dispute.round,
+            draw_slot: dispute.draw_slot,
+        });
+
+        Ok(())
+    }
+
+    // Vote on the consumer's share of a disputed escrow (by a panel member)
+    pub fn vote_panel(ctx: Context<VotePanel>, consumer_share: u8) -> Result<()> {
+        let panel = &mut ctx.accounts.panel;
+        let arbitrator = &ctx.accounts.arbitrator;
+        
+        require!(
+            consumer_share <= 100,
+            TransactionError::InvalidShares
+        );
+        
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            !panel.finalized && current_time <= panel.voting_deadline,
+            TransactionError::PanelVotingClosed
+        );
+        
+        // Members vote once all the evidence is in
+        require!(
+            current_time > ctx.accounts.dispute.evidence_deadline,
+            TransactionError::EvidencePeriodOpen
+        );
+        
+        let member = panel
+            .members
+            .iter_mut()
+            .find(|m| m.arbitrator == arbitrator.key())
+            .ok_or(TransactionError::NotPanelMember)?;
+        
+        require!(
+            !member.voted,
+            TransactionError::AlreadyVoted
+        );
+        
+        member.consumer_share = consumer_share;
+        member.voted = true;
+        
+        emit!(PanelVoteCast {
+            escrow_id: panel.escrow,
+            arbitrator: arbitrator.key(),
+            consumer_share,
+        });
+
+        Ok(())
+    }
+
+    // Apply the panel's median vote once everyone has voted or voting has
+    // closed with a quorum. Anyone can call this. A first-round vote becomes
+    // an appealable ruling; an appeal panel's vote is settled immediately. remaining_accounts holds,
+    // for each member in panel order, their Arbitrator account and stake
+    // vault (both writable). Members who voted far from the outcome are slashed.
+    pub fn finalize_panel<'info>(ctx: Context<'_, '_, '_, 'info, FinalizePanel<'info>>) -> Result<()> {
+        let panel = &mut ctx.accounts.panel;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            !panel.finalized,
+            TransactionError::PanelVotingClosed
+        );
+        
+        let mut votes: Vec<u8> = panel
+            .members
+            .iter()
+            .filter(|m| m.voted)
+            .map(|m| m.consumer_share)
+            .collect();
+        
+        require!(
+            votes.len() == panel.members.len() || current_time > panel.voting_deadline,
+            TransactionError::PanelVotingOpen
+        );
+        
+        require!(
+            votes.len() > panel.members.len() / 2,
+            TransactionError::PanelQuorumNotReached
+        );
+        
+        votes.sort_unstable();
+        let consumer_share = votes[(votes.len() - 1) / 2];
+        panel.finalized = true;
+        
+        if panel.round == 0 {
+            issue_ruling(
+                &mut ctx.accounts.settlement.escrow,
+                &mut ctx.accounts.settlement.dispute,
+                panel.key(),
+                consumer_share,
+            )?;
+        } else {
+            // An appeal panel's decision is final
+            let settlement = &ctx.accounts.settlement;
+            let bond_returned = if settlement.dispute.appellant == settlement.escrow.consumer {
+                consumer_share > settlement.dispute.ruling_consumer_share
+            } else {
+                consumer_share < settlement.dispute.ruling_consumer_share
+            };
+            
+            settle_dispute(
+                &mut ctx.accounts.settlement,
+                *ctx.bumps.get("escrow_authority").unwrap(),
+                *ctx.bumps.get("match_authority").unwrap(),
+                consumer_share,
+                100 - consumer_share,
+                bond_returned,
+            )?;
+            
+            emit!(DisputeResolved {
+                escrow_id: panel.escrow,
+                match_id: ctx.accounts.settlement.escrow.match_id,
+                resolved_by: panel.key(),
+                consumer_share,
+                provider_share: 100 - consumer_share,
+            });
+        }
+        
+        require!(
+            ctx.remaining_accounts.len() == panel.members.len() * 2,
+            TransactionError::InvalidPanelAccounts
+        );
+        
+        let config = &ctx.accounts.settlement.fees.config;
+        let seeds = &[b"config".as_ref(), &[config.bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        for (i, member) in panel.members.iter().enumerate() {
+            let mut arbitrator: Account<Arbitrator> = Account::try_from(&ctx.remaining_accounts[i * 2])?;
+            let stake_vault: Account<TokenAccount> = Account::try_from(&ctx.remaining_accounts[i * 2 + 1])?;
+            
+            require!(
+                arbitrator.authority == member.arbitrator && arbitrator.stake_vault == stake_vault.key(),
+                TransactionError::InvalidPanelAccounts
+            );
+            
+            arbitrator.active_panels -= 1;
+            
+            if member.voted {
+                arbitrator.resolved_count += 1;
+                
+                if member.consumer_share.abs_diff(consumer_share) > CONSENSUS_TOLERANCE {
+                    let slashed = bps_of(arbitrator.stake, SLASH_BPS);
+                    arbitrator.stake -= slashed;
+                    arbitrator.slashed_count += 1;
+                    
+                    transfer_from_vault(
+                        &stake_vault,
+                        &ctx.accounts.stake_insurance_fund,
+                        &config.to_account_info(),
+                        &ctx.accounts.settlement.token_program,
+                        signer_seeds,
+                        slashed,
+                    )?;
+                    
+                    emit!(ArbitratorSlashed {
+                        authority: arbitrator.authority,
+                        escrow_id: panel.escrow,
+                        amount: slashed,
+                    });
+                }
+            }
+            
+            arbitrator.exit(ctx.program_id)?;
+        }
+
+        Ok(())
+    }
+
+    // Apply the configured default split to a dispute nobody decided in time.
+    // Anyone can call this. A single arbitrator is recorded as having missed
+    // the dispute; for a drawn panel that failed to reach quorum,
+    // remaining_accounts holds each member's Arbitrator account (writable) in
+    // panel order and the members who did not vote are recorded instead.
+    pub fn resolve_dispute_by_default<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveDisputeByDefault<'info>>,
+    ) -> Result<()> {
+        let escrow = &ctx.accounts.settlement.escrow;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            escrow.status == EscrowStatus::Disputed,
+            TransactionError::InvalidEscrowStatus
+        );
+        
+        require!(
+            current_time > ctx.accounts.settlement.dispute.resolution_deadline,
+            TransactionError::DisputeDeadlineNotReached
+        );
+        
+        let escrow_key = escrow.key();
+        if escrow.panel_dispute {
+            // A panel may never have been drawn if too few arbitrators were eligible
+            let panel_info = ctx.accounts.panel.to_account_info();
+            if !panel_info.data_is_empty() {
+                let mut panel: Account<DisputePanel> = Account::try_from(&panel_info)?;
+                let votes = panel.members.iter().filter(|m| m.voted).count();
+                
+                // A panel with a quorum must be finalized through its vote
+                require!(
+                    current_time > panel.voting_deadline && votes <= panel.members.len() / 2,
+                    TransactionError::PanelVotingOpen
+                );
+                
+                require!(
+                    ctx.remaining_accounts.len() == panel.members.len(),
+                    TransactionError::InvalidPanelAccounts
+                );
+                
+                for (member, info) in panel.members.iter().zip(ctx.remaining_accounts.iter()) {
+                    let mut arbitrator: Account<Arbitrator> = Account::try_from(info)?;
+                    require!(
+                        arbitrator.authority == member.arbitrator,
+                        TransactionError::InvalidPanelAccounts
+                    );
+                    
+                    arbitrator.active_panels -= 1;
+                    if !member.voted {
+                        arbitrator.missed_count += 1;
+                        
+                        emit!(ArbitratorMissedDeadline {
+                            authority: arbitrator.authority,
+                            escrow_id: escrow_key,
+                        });
+                    }
+                    arbitrator.exit(ctx.program_id)?;
+                }
+                
+                panel.finalized = true;
+                panel.exit(ctx.program_id)?;
+            }
+        } else {
+            let arbitrator = ctx
+                .accounts
+                .arbitrator_record
+                .as_mut()
+                .ok_or(TransactionError::MissingArbitrator)?;
+            
+            require!(
+                arbitrator.authority == escrow.arbitrator,
+                TransactionError::ArbitratorMismatch
+            );
+            
+            arbitrator.missed_count += 1;
+            
+            emit!(ArbitratorMissedDeadline {
+                authority: arbitrator.authority,
+                escrow_id: escrow_key,
+            });
+        }
+        
+        let consumer_share = ctx.accounts.settlement.fees.config.default_consumer_share;
+        
+        // An appellant is not penalised for the panel failing to decide
+        settle_dispute(
+            &mut ctx.accounts.settlement,
+            *ctx.bumps.get("escrow_authority").unwrap(),
+            *ctx.bumps.get("match_authority").unwrap(),
+            consumer_share,
+            100 - consumer_share,
+            true,
+        )?;
+        
+        emit!(DisputeResolved {
+            escrow_id: escrow_key,
+            match_id: ctx.accounts.settlement.escrow.match_id,
+            resolved_by: ctx.accounts.caller.key(),
+            consumer_share,
+            provider_share: 100 - consumer_share,
+        });
+
+        Ok(())
+    }
+}
+
+// Record a ruling on a disputed escrow and open its appeal window
+fn issue_ruling<'info>(
+    escrow: &mut Account<'info, Escrow>,
+    dispute: &mut Account<'info, Dispute>,
+    ruled_by: Pubkey,
+    consumer_share: u8,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Both parties get the whole evidence period
+    require!(
+        current_time > dispute.evidence_deadline,
+        TransactionError::EvidencePeriodOpen
+    );
+    
+    escrow.status = EscrowStatus::Ruled;
+    escrow.updated_at = current_time;
+    
+    dispute.ruling_consumer_share = consumer_share;
+    dispute.ruled_by = ruled_by;
+    dispute.appeal_deadline = current_time + APPEAL_WINDOW;
+    
+    emit!(RulingIssued {
+        escrow_id: escrow.key(),
+        ruled_by,
+        consumer_share,
+        provider_share: 100 - consumer_share,
+        appeal_deadline: dispute.appeal_deadline,
+    });
+
+    Ok(())
+}
+
+// Pay out a disputed milestone according to the decided split and record
+// the ruling, closing the vault once every milestone is settled
+fn settle_milestone_dispute<'info>(
+    accounts: &mut MilestoneSettlement<'info>,
+    escrow_authority_bump: u8,
+    index: u8,
+    consumer_share: u8,
+    resolved_by: Pubkey,
+) -> Result<()> {
+    let escrow = &mut accounts.escrow;
+    
+    require!(
+        escrow.status == EscrowStatus::Created,
+        TransactionError::InvalidEscrowStatus
+    );
+    
+    let milestone = escrow
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(TransactionError::InvalidMilestoneIndex)?;
+    
+    require!(
+        milestone.status == MilestoneStatus::Disputed,
+        TransactionError::InvalidMilestoneStatus
+    );
+    
+    milestone.status = MilestoneStatus::Resolved;
+    let amount = milestone.amount;
+    let consumer_amount = (amount as u128 * consumer_share as u128 / 100) as u64;
+    let provider_amount = amount - consumer_amount;
+    
+    escrow.released_amount += amount;
+    escrow.refunded_amount += consumer_amount;
+    escrow.updated_at = Clock::get()?.unix_timestamp;
+    
+    accounts.dispute.ruling_consumer_share = consumer_share;
+    accounts.dispute.ruled_by = resolved_by;
+    
+    let escrow_key = escrow.key();
+    let seeds = &[
+        b"escrow".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let settled = escrow.milestones_settled();
+    refund_consumer(
+        &accounts.escrow_token_account,
+        accounts.consumer_token_account.as_ref(),
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+        consumer_amount,
+        settled,
+    )?;
+    
+    pay_provider(
+        &accounts.escrow_token_account,
+        &accounts.provider_token_account,
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+        &accounts.fees,
+        accounts.match_record.matcher,
+        provider_amount,
+    )?;
+    
+    if settled {
+        escrow.status = EscrowStatus::Released;
+        
+        close_vault(
+            &accounts.escrow_token_account,
+            accounts.consumer_token_account.as_ref(),
+            &accounts.consumer,
+            &accounts.escrow_authority,
+            &accounts.token_program,
+            signer_seeds,
+        )?;
+    }
+    
+    emit!(MilestoneDisputeResolved {
+        escrow_id: escrow_key,
+        match_id: escrow.match_id,
+        index,
+        resolved_by,
+        consumer_share,
+        provider_share: 100 - consumer_share,
+    });
+
+    Ok(())
+}
+
+// Pay out a disputed escrow according to the decided split, settle any
+// appeal bond, close its vault and move the match to its terminal resolved state
+fn settle_dispute<'info>(
+    accounts: &mut DisputeSettlement<'info>,
+    escrow_authority_bump: u8,
+    match_authority_bump: u8,
+    consumer_share: u8,
+    provider_share: u8,
+    bond_returned: bool,
+) -> Result<()> {
+    let escrow = &mut accounts.escrow;
+    
+    // Validate shares
+    require!(
+        consumer_share as u16 + provider_share as u16 == 100,
+        TransactionError::InvalidShares
+    );
+    
+    // Update escrow status
+    escrow.status = EscrowStatus::Resolved;
+    escrow.updated_at = Clock::get()?.unix_timestamp;
+    
+    // Calculate amounts; anything already streamed to the provider stays paid
+    let remaining = escrow.remaining();
+    let consumer_amount = (remaining as u128 * consumer_share as u128 / 100) as u64;
+    let provider_amount = remaining - consumer_amount;
+    escrow.released_amount = escrow.amount;
+    escrow.refunded_amount += consumer_amount;
+    
+    let escrow_key = escrow.key();
+    let seeds = &[
+        b"escrow".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    // Transfer consumer share
+    refund_consumer(
+        &accounts.escrow_token_account,
+        accounts.consumer_token_account.as_ref(),
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+        consumer_amount,
+        true,
+    )?;
+    
+    // Transfer provider share
+    pay_provider(
+        &accounts.escrow_token_account,
+        &accounts.provider_token_account,
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+        &accounts.fees,
+        accounts.match_record.matcher,
+        provider_amount,
+    )?;
+    
+    // The appeal bond sits in the vault alongside the escrowed funds
+    let appeal_bond = accounts.dispute.appeal_bond;
+    if appeal_bond > 0 {
+        if bond_returned {
+            let appellant_token_account = accounts
+                .appellant_token_account
+                .as_ref()
+                .ok_or(TransactionError::MissingTokenAccount)?;
+            transfer_from_vault(
+                &accounts.escrow_token_account,
+                appellant_token_account,
+                &accounts.escrow_authority,
+                &accounts.token_program,
+                signer_seeds,
+                appeal_bond,
+            )?;
+        } else {
+            transfer_from_vault(
+                &accounts.escrow_token_account,
+                &accounts.fees.treasury,
+                &accounts.escrow_authority,
+                &accounts.token_program,
+                signer_seeds,
+                appeal_bond,
+            )?;
+        }
+        
+        emit!(AppealBondSettled {
+            escrow_id: escrow_key,
+            appellant: accounts.dispute.appellant,
+            amount: appeal_bond,
+            returned: bond_returned,
+        });
+    }
+    
+    refund_pending_extension(
+        escrow,
+        accounts.extension.as_ref(),
+        &accounts.escrow_token_account,
+        accounts.consumer_token_account.as_ref(),
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+    )?;
+    
+    // The escrow is settled, so return the vault rent to the consumer
+    close_vault(
+        &accounts.escrow_token_account,
+        accounts.consumer_token_account.as_ref(),
+        &accounts.consumer,
+        &accounts.escrow_authority,
+        &accounts.token_program,
+        signer_seeds,
+    )?;
+    
+    // Move the match to its terminal resolved state
+    let match_key = accounts.match_record.key();
+    let seeds = &[
+        b"match_authority".as_ref(),
+        match_key.as_ref(),
+        &[match_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let cpi_accounts = matching_engine::cpi::accounts::ResolveMatchDispute {
+        match_record: accounts.match_record.to_account_info(),
+        match_authority: accounts.match_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.matching_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    matching_engine::cpi::resolve_match_dispute(cpi_ctx, consumer_share, provider_share)
+}
+
+// Seed a panel draw with the hash of the first slot produced at or after
+// `draw_slot`. SlotHashes only keeps recent slots, so an older entry must
+// still be present to show that no earlier qualifying slot has aged out.
+fn panel_seed(
+    slot_hashes: &AccountInfo,
+    draw_slot: u64,
+    escrow: &Pubkey,
+    round: u8,
+) -> Result<[u8; 32]> {
+    // SlotHashes data: u64 entry count, then (slot: u64, hash: [u8; 32])
+    // entries, newest first
+    let data = slot_hashes.try_borrow_data()?;
+    require!(
+        data.len() >= 8,
+        TransactionError::PanelDrawPending
+    );
+    let mut count_bytes = [0u8; 8];
+    count_bytes.copy_from_slice(&data[..8]);
+    let count = (u64::from_le_bytes(count_bytes) as usize).min((data.len() - 8) / 40);
+    
+    let mut seed_hash: Option<&[u8]> = None;
+    for i in 0..count {
+        let entry = &data[8 + i * 40..8 + (i + 1) * 40];
+        let mut slot_bytes = [0u8; 8];
+        slot_bytes.copy_from_slice(&entry[..8]);
+        if u64::from_le_bytes(slot_bytes) < draw_slot {
+            let hash = seed_hash.ok_or(TransactionError::PanelDrawPending)?;
+            return Ok(hashv(&[hash, escrow.as_ref(), &[round]]).to_bytes());
+        }
+        seed_hash = Some(&entry[8..40]);
+    }
+    
+    // Every recorded slot is at or after the draw slot, so the qualifying
+    // slot may no longer be recorded
+    err!(TransactionError::PanelSeedExpired)
+}
+
+fn draw_index(seed: &[u8; 32], attempt: u64, count: u32) -> u32 {
+    let hash = hashv(&[seed.as_ref(), &attempt.to_le_bytes()]).to_bytes();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    (u64::from_le_bytes(bytes) % count as u64) as u32
+}
+
+// A replicated match is only payable once its group has reached a quorum
+// and the match's committed result is the agreed one
+fn check_replication(
+    match_record: &Match,
+    replication_group: Option<&Account<ReplicationGroup>>,
+) -> Result<()> {
+    if match_record.replication_group == Pubkey::default() {
+        return Ok(());
+    }
+    
+    // A group that expired without a quorum leaves each replica to settle
+    // on its own
+    let group = replication_group.ok_or(TransactionError::ReplicationGroupMismatch)?;
+    require!(
+        group.failed || (group.finalized && group.consensus_hash == match_record.result_hash),
+        TransactionError::ResultNotVerified
+    );
+
+    Ok(())
+}
+
+// Move a consumer's payment into an escrow vault, wrapping native SOL when
+// the vault holds wSOL
+fn deposit_to_vault<'info>(
+    consumer: &Signer<'info>,
+    consumer_token_account: Option<&Account<'info, TokenAccount>>,
+    vault: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if vault.mint == native_mint::ID {
+        // Wrap native SOL straight into the vault so the consumer does
+        // not need a wSOL account; payouts then work as for any SPL mint
+        let cpi_accounts = system_program::Transfer {
+            from: consumer.to_account_info(),
+            to: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        
+        system_program::transfer(cpi_ctx, amount)?;
+        
+        let cpi_accounts = SyncNative {
+            account: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        
+        token::sync_native(cpi_ctx)
+    } else {
+        let consumer_token_account =
+            consumer_token_account.ok_or(TransactionError::MissingTokenAccount)?;
+        
+        // Transfer tokens from consumer to escrow account
+        let cpi_accounts = Transfer {
+            from: consumer_token_account.to_account_info(),
+            to: vault.to_account_info(),
+            authority: consumer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        
+        token::transfer(cpi_ctx, amount)
+    }
+}
+
+fn transfer_from_vault<'info>(
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let cpi_accounts = Transfer {
+        from: vault.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.clone(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    token::transfer(cpi_ctx, amount)
+}
+
+// Refund the consumer out of the vault. A native-SOL refund without a
+// consumer token account stays in the vault and is unwrapped into the
+// consumer's wallet when the vault is closed, so it is only allowed when the
+// caller closes the vault afterwards.
+fn refund_consumer<'info>(
+    vault: &Account<'info, TokenAccount>,
+    consumer_token_account: Option<&Account<'info, TokenAccount>>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+    closing_vault: bool,
+) -> Result<()> {
+    match consumer_token_account {
+        Some(consumer_token_account) => transfer_from_vault(
+            vault,
+            consumer_token_account,
+            authority,
+            token_program,
+            signer_seeds,
+            amount,
+        ),
+        None => {
+            require!(
+                amount == 0 || (closing_vault && vault.mint == native_mint::ID),
+                TransactionError::MissingTokenAccount
+            );
+            Ok(())
+        }
+    }
+}
+
+// Return a still-pending extension's top-up to the consumer before the
+// escrow's vault is closed. The proposal itself is closed by the accounts
+// struct.
+fn refund_pending_extension<'info>(
+    escrow: &mut Escrow,
+    extension: Option<&Account<'info, ExtensionProposal>>,
+    vault: &Account<'info, TokenAccount>,
+    consumer_token_account: Option<&Account<'info, TokenAccount>>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if !escrow.extension_pending {
+        return Ok(());
+    }
+    
+    let extension = extension.ok_or(TransactionError::MissingExtension)?;
+    escrow.extension_pending = false;
+    
+    refund_consumer(
+        vault,
+        consumer_token_account,
+        authority,
+        token_program,
+        signer_seeds,
+        extension.top_up,
+        true,
+    )
+}
+
+// Pay the provider out of the vault, skimming the platform fee and routing
+// its shares to the rewards pool, the matcher, the insurance fund and the
+// treasury. The matcher's token account must be supplied whenever the
+// matcher has a share.
+fn pay_provider<'info>(
+    vault: &Account<'info, TokenAccount>,
+    provider_token_account: &Account<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+    fees: &FeeAccounts<'info>,
+    matcher: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let config = &fees.config;
+    
+    require!(
+        fees.allowed_mint.mint == vault.mint,
+        TransactionError::InvalidMint
+    );
+    
+    let fee = bps_of(amount, config.fee_bps);
+    let rewards_amount = bps_of(fee, config.rewards_share_bps);
+    // The caller cannot divert the matcher's share by leaving its account out
+    let matcher_token_account = if config.matcher_share_bps > 0 {
+        let matcher_token_account = fees
+            .matcher_token_account
+            .as_ref()
+            .ok_or(TransactionError::MissingTokenAccount)?;
+        require!(
+            matcher_token_account.owner == matcher,
+            TransactionError::MatcherMismatch
+        );
+        require!(
+            matcher_token_account.mint == vault.mint,
+            TransactionError::InvalidMint
+        );
+        Some(matcher_token_account)
+    } else {
+        None
+    };
+    let matcher_amount = bps_of(fee, config.matcher_share_bps);
+    let insurance_amount = bps_of(fee, config.insurance_share_bps);
+    let treasury_amount = fee - rewards_amount - matcher_amount - insurance_amount;
+    
+    transfer_from_vault(vault, provider_token_account, authority, token_program, signer_seeds, amount - fee)?;
+    transfer_from_vault(vault, &fees.rewards_pool, authority, token_program, signer_seeds, rewards_amount)?;
+    if let Some(matcher_token_account) = matcher_token_account {
+        transfer_from_vault(vault, matcher_token_account, authority, token_program, signer_seeds, matcher_amount)?;
+    }
+    transfer_from_vault(vault, &fees.insurance_fund, authority, token_program, signer_seeds, insurance_amount)?;
+    transfer_from_vault(vault, &fees.treasury, authority, token_program, signer_seeds, treasury_amount)?;
+    
+    if fee > 0 {
+        emit!(FeeCollected {
+            mint: vault.mint,
+            amount,
+            fee,
+            rewards_amount,
+            matcher_amount,
+            insurance_amount,
+            treasury_amount,
+        });
+    }
+    
+    Ok(())
+}
+
+// Assign the arbitrator handling an escrow's disputes. The arbitrator is
+// drawn from the registry using the escrow address, so neither party can pick
+// it. `candidates` holds Arbitrator accounts in draw order; each must sit at
+// the index drawn for its attempt, and inactive or conflicted candidates are
+// skipped. An escrow keeps the arbitrator assigned at its first dispute.
+fn assign_arbitrator<'info>(
+    escrow: &mut Account<'info, Escrow>,
+    candidates: &[AccountInfo<'info>],
+    arbitrator_count: u32,
+) -> Result<()> {
+    if escrow.arbitrator != Pubkey::default() {
+        return Ok(());
+    }
+    
+    require!(
+        arbitrator_count > 0,
+        TransactionError::MissingArbitrator
+    );
+    
+    let seed = hashv(&[b"arbitrator".as_ref(), escrow.key().as_ref()]).to_bytes();
+    for (attempt, info) in candidates.iter().enumerate() {
+        require!(
+            attempt < MAX_DRAW_ATTEMPTS,
+            TransactionError::MissingArbitrator
+        );
+        
+        let candidate: Account<Arbitrator> = Account::try_from(info)?;
+        require!(
+            candidate.index == draw_index(&seed, attempt as u64, arbitrator_count),
+            TransactionError::PanelDrawMismatch
+        );
+        
+        if candidate.active
+            && candidate.authority != escrow.consumer
+            && candidate.authority != escrow.provider
+        {
+            escrow.arbitrator = candidate.authority;
+            return Ok(());
+        }
+    }
+    
+    err!(TransactionError::MissingArbitrator)
+}
+
+fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+fn close_vault<'info>(
+    vault: &Account<'info, TokenAccount>,
+    consumer_token_account: Option<&Account<'info, TokenAccount>>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // SPL only closes an empty token account, so anything sent to the vault
+    // outside the escrow goes to the consumer first. A native vault closes
+    // with its balance, which is unwrapped into `destination`.
+    let stray = {
+        let info = vault.to_account_info();
+        let data = info.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+    if stray > 0 && vault.mint != native_mint::ID {
+        let consumer_token_account =
+            consumer_token_account.ok_or(TransactionError::MissingTokenAccount)?;
+        transfer_from_vault(
+            vault,
+            consumer_token_account,
+            authority,
+            token_program,
+            signer_seeds,
+            stray,
+        )?;
+    }
+    
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: destination.clone(),
+        authority: authority.clone(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    token::close_account(cpi_ctx)
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(init, payer = admin, space = 8 + Config::LEN, seeds = [b"config".as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, TransactionManager>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ TransactionError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddAllowedMint<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowedMint::LEN,
+        seeds = [b"allowed_mint".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"treasury".as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = config
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"insurance".as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = config
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+    #[account(constraint = rewards_pool.mint == mint.key() @ TransactionError::InvalidMint)]
+    pub rewards_pool: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintEnabled<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"allowed_mint".as_ref(), allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddArbitrator<'info> {
+    #[account(mut, seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Arbitrator::LEN,
+        seeds = [b"arbitrator".as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub arbitrator: Account<'info, Arbitrator>,
+    /// CHECK: The key that will sign as arbitrator
+    pub authority: AccountInfo<'info>,
+    #[account(address = config.stake_mint @ TransactionError::InvalidMint)]
+    pub stake_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"arbitrator_stake".as_ref(), authority.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveArbitrator<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), arbitrator.authority.as_ref()],
+        bump = arbitrator.bump
+    )]
+    pub arbitrator: Account<'info, Arbitrator>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeConfig<'info> {
+    #[account(mut, seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(mut, address = allowed_mint.treasury @ TransactionError::TreasuryMismatch)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Accounts that receive the platform fee on every provider payout
+#[derive(Accounts)]
+pub struct FeeAccounts<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(mut, address = allowed_mint.treasury @ TransactionError::TreasuryMismatch)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, address = allowed_mint.rewards_pool @ TransactionError::TreasuryMismatch)]
+    pub rewards_pool: Account<'info, TokenAccount>,
+    #[account(mut, address = allowed_mint.insurance_fund @ TransactionError::TreasuryMismatch)]
+    pub insurance_fund: Account<'info, TokenAccount>,
+    // Only needed when the config gives the matcher a share of the fee
+    #[account(mut)]
+    pub matcher_token_account: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateEscrow<'info> {
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + Escrow::LEN,
+        seeds = [b"match_escrow".as_ref(), match_record.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub match_record: Account<'info, Match>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    // Not needed when the escrow is funded with native SOL
+    #[account(
+        mut,
+        constraint = consumer_token_account.mint == mint.key() @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(address = match_record.payment_mint @ TransactionError::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), mint.key().as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = consumer,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow_authority
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeExtension<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + ExtensionProposal::LEN,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump
+    )]
+    pub extension: Account<'info, ExtensionProposal>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: Signer<'info>,
+    // Not needed when the escrow holds native SOL
+    #[account(
+        mut,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptExtension<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(address = match_record.resource @ TransactionError::ResourceMismatch)]
+    pub resource: Account<'info, Resource>,
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Account<'info, ExtensionProposal>,
+    /// CHECK: Receives the proposal rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    pub provider: Signer<'info>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    pub matching_program: Program<'info, MatchingEngine>,
+}
+
+#[derive(Accounts)]
+pub struct CancelExtension<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Account<'info, ExtensionProposal>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the proposal rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Account<'info, TokenAccount>,
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseFunds<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub signer: Signer<'info>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TerminateMatch<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    // Also funds the cancellation fee when the provider terminates before
+    // earning enough to cover it
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub fees: FeeAccounts<'info>,
+    pub signer: Signer<'info>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = matching_engine::ID
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist; closed by matching_engine
+    pub payload_key: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub matching_program: Program<'info, MatchingEngine>,
+}
+
+#[derive(Accounts)]
+pub struct RefundEscrow<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    pub signer: Signer<'info>,
+    // Only needed when the assigned arbitrator refunds a disputed escrow
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), arbitrator.authority.as_ref()],
+        bump = arbitrator.bump
+    )]
+    pub arbitrator: Option<Account<'info, Arbitrator>>,
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimNoShow<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(
+        mut,
+        seeds = [b"provider_profile".as_ref(), escrow.provider.as_ref()],
+        bump = provider_profile.bump,
+        seeds::program = resource_manager::ID
+    )]
+    pub provider_profile: Account<'info, ProviderProfile>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    #[account(seeds = [b"reputation_authority".as_ref()], bump)]
+    /// CHECK: This PDA signs reputation updates in resource_manager
+    pub reputation_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = matching_engine::ID
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist; closed by matching_engine
+    pub payload_key: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub matching_program: Program<'info, MatchingEngine>,
+    pub resource_program: Program<'info, ResourceManager>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAccrued<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub provider: Signer<'info>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StopStream<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    pub fees: FeeAccounts<'info>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = matching_engine::ID
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist; closed by matching_engine
+    pub payload_key: UncheckedAccount<'info>,
+    pub matching_program: Program<'info, MatchingEngine>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub signer: Signer<'info>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct DisputeMilestone<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Dispute::LEN,
+        seeds = [b"milestone_dispute".as_ref(), escrow.key().as_ref(), &[index]],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+// Accounts needed to pay out a disputed milestone
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct MilestoneSettlement<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"milestone_dispute".as_ref(), escrow.key().as_ref(), &[index]],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMilestoneDispute<'info> {
+    pub settlement: MilestoneSettlement<'info>,
+    #[account(address = settlement.escrow.arbitrator @ TransactionError::ArbitratorMismatch)]
+    pub arbitrator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), arbitrator.key().as_ref()],
+        bump = arbitrator_record.bump,
+        constraint = arbitrator_record.active @ TransactionError::InactiveArbitrator
+    )]
+    pub arbitrator_record: Account<'info, Arbitrator>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMilestoneDisputeByDefault<'info> {
+    pub settlement: MilestoneSettlement<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), settlement.escrow.arbitrator.as_ref()],
+        bump = arbitrator_record.bump
+    )]
+    pub arbitrator_record: Account<'info, Arbitrator>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleUsage<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"config".as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    pub provider: Signer<'info>,
+    pub attestor: Signer<'info>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeEscrow<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed to dispute a replica its replication group outvoted
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Dispute::LEN,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), escrow.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"payload_key".as_ref(), match_record.key().as_ref()],
+        bump,
+        seeds::program = matching_engine::ID
+    )]
+    /// CHECK: The match's payload key PDA, which may not exist; closed by matching_engine
+    pub payload_key: UncheckedAccount<'info>,
+    /// CHECK: Receives the payload key rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    pub matching_program: Program<'info, MatchingEngine>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    pub escrow: Account<'info, Escrow>,
+    // The escrow's dispute or one of its milestone disputes
+    #[account(mut, constraint = dispute.escrow == escrow.key() @ TransactionError::DisputeMismatch)]
+    pub dispute: Account<'info, Dispute>,
+    pub submitter: Signer<'info>,
+}
+
+// Accounts needed to pay out a disputed escrow and resolve its match
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut, address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    #[account(seeds = [b"match_authority".as_ref(), match_record.key().as_ref()], bump)]
+    /// CHECK: This PDA signs match status updates in matching_engine
+    pub match_authority: AccountInfo<'info>,
+    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
+    /// CHECK: This is a PDA that manages the escrow
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the vault rent, checked against the escrow
+    #[account(mut, address = escrow.consumer @ TransactionError::ConsumerMismatch)]
+    pub consumer: AccountInfo<'info>,
+    // Not needed when a native-SOL refund is unwrapped into the consumer's wallet
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == escrow.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Option<Account<'info, TokenAccount>>,
+    // Only needed while an extension proposal is pending
+    #[account(
+        mut,
+        close = consumer,
+        seeds = [b"extension".as_ref(), escrow.key().as_ref()],
+        bump = extension.bump
+    )]
+    pub extension: Option<Account<'info, ExtensionProposal>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == escrow.provider @ TransactionError::ProviderMismatch,
+        constraint = provider_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    // Only needed to return an appeal bond
+    #[account(
+        mut,
+        constraint = appellant_token_account.owner == dispute.appellant @ TransactionError::AppellantMismatch,
+        constraint = appellant_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub appellant_token_account: Option<Account<'info, TokenAccount>>,
+    pub fees: FeeAccounts<'info>,
+    pub token_program: Program<'info, Token>,
+    pub matching_program: Program<'info, MatchingEngine>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub arbitrator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), arbitrator.key().as_ref()],
+        bump = arbitrator_record.bump,
+        constraint = arbitrator_record.active @ TransactionError::InactiveArbitrator
+    )]
+    pub arbitrator_record: Account<'info, Arbitrator>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRuling<'info> {
+    pub settlement: DisputeSettlement<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AppealRuling<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), escrow.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = appellant_token_account.owner == appellant.key() @ TransactionError::AppellantMismatch,
+        constraint = appellant_token_account.mint == escrow.mint @ TransactionError::InvalidMint
+    )]
+    pub appellant_token_account: Account<'info, TokenAccount>,
+    pub appellant: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDisputeByDefault<'info> {
+    pub settlement: DisputeSettlement<'info>,
+    // Required unless the dispute went to a panel
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), arbitrator_record.authority.as_ref()],
+        bump = arbitrator_record.bump
+    )]
+    pub arbitrator_record: Option<Account<'info, Arbitrator>>,
+    #[account(
+        mut,
+        seeds = [
+            b"panel".as_ref(),
+            settlement.escrow.key().as_ref(),
+            &[settlement.dispute.round],
+        ],
+        bump
+    )]
+    /// CHECK: The current round's panel PDA, which may not exist yet
+    pub panel: UncheckedAccount<'info>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeArbitration<'info> {
+    #[account(mut, seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub stake_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"stake_insurance".as_ref(), stake_mint.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = config
+    )]
+    pub stake_insurance_fund: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPanelThreshold<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"allowed_mint".as_ref(), allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCoverageLimit<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"allowed_mint".as_ref(), allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FileInsuranceClaim<'info> {
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(address = escrow.match_id @ TransactionError::MatchMismatch)]
+    pub match_record: Account<'info, Match>,
+    // Only needed for replicated matches
+    #[account(address = match_record.replication_group @ TransactionError::ReplicationGroupMismatch)]
+    pub replication_group: Option<Account<'info, ReplicationGroup>>,
+    // Escrow or milestone dispute whose ruling the claim rests on
+    #[account(constraint = dispute.escrow == escrow.key() @ TransactionError::DisputeMismatch)]
+    pub dispute: Option<Account<'info, Dispute>>,
+    #[account(
+        init,
+        payer = consumer,
+        space = 8 + InsuranceClaim::LEN,
+        seeds = [b"insurance_claim".as_ref(), escrow.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, InsuranceClaim>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), escrow.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewInsuranceClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_claim".as_ref(), escrow.key().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, InsuranceClaim>,
+    #[account(address = claim.escrow @ TransactionError::ClaimMismatch)]
+    pub escrow: Account<'info, Escrow>,
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"allowed_mint".as_ref(), claim.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    #[account(mut, address = allowed_mint.insurance_fund @ TransactionError::TreasuryMismatch)]
+    pub insurance_fund: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = consumer_token_account.owner == claim.consumer @ TransactionError::ConsumerMismatch,
+        constraint = consumer_token_account.mint == claim.mint @ TransactionError::InvalidMint
+    )]
+    pub consumer_token_account: Account<'info, TokenAccount>,
+    pub arbitrator: Signer<'info>,
+    #[account(
+        seeds = [b"arbitrator".as_ref(), arbitrator.key().as_ref()],
+        bump = arbitrator_record.bump,
+        constraint = arbitrator_record.active @ TransactionError::InactiveArbitrator
+    )]
+    pub arbitrator_record: Account<'info, Arbitrator>,
+    // Only needed when the escrow's dispute went to a panel
+    #[account(constraint = panel.escrow == escrow.key() @ TransactionError::DisputeMismatch)]
+    pub panel: Option<Account<'info, DisputePanel>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeArbitrator<'info> {
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), authority.key().as_ref()],
+        bump = arbitrator.bump
+    )]
+    pub arbitrator: Account<'info, Arbitrator>,
+    #[account(mut, address = arbitrator.stake_vault @ TransactionError::StakeVaultMismatch)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeArbitrator<'info> {
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"arbitrator".as_ref(), authority.key().as_ref()],
+        bump = arbitrator.bump
+    )]
+    pub arbitrator: Account<'info, Arbitrator>,
+    #[account(mut, address = arbitrator.stake_vault @ TransactionError::StakeVaultMismatch)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DrawPanel<'info> {
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(seeds = [b"config".as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DisputePanel::LEN,
+        seeds = [b"panel".as_ref(), escrow.key().as_ref(), &[dispute.round]],
+        bump
+    )]
+    pub panel: Account<'info, DisputePanel>,
+    /// CHECK: Checked against the SlotHashes sysvar address
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReschedulePanelDraw<'info> {
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"dispute".as_ref(), escrow.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        seeds = [b"panel".as_ref(), escrow.key().as_ref(), &[dispute.round]],
+        bump
+    )]
+    /// CHECK: The round's panel PDA, which must not exist yet
+    pub panel: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VotePanel<'info> {
+    #[account(
+        mut,
+        seeds = [b"panel".as_ref(), panel.escrow.as_ref(), &[panel.round]],
+        bump = panel.bump
+    )]
+    pub panel: Account<'info, DisputePanel>,
+    #[account(
+        seeds = [b"dispute".as_ref(), panel.escrow.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub arbitrator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizePanel<'info> {
+    pub settlement: DisputeSettlement<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"panel".as_ref(),
+            settlement.escrow.key().as_ref(),
+            &[settlement.dispute.round],
+        ],
+        bump = panel.bump
+    )]
+    pub panel: Account<'info, DisputePanel>,
+    #[account(
+        mut,
+        seeds = [b"stake_insurance".as_ref(), settlement.fees.config.stake_mint.as_ref()],
+        bump
+    )]
+    pub stake_insurance_fund: Account<'info, TokenAccount>,
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,                    // Administrative authority
+    pub usage_oracle: Pubkey,             // Oracle allowed to attest usage hours
+    pub fee_bps: u16,                     // Platform fee on provider payouts (basis points)
+    pub rewards_share_bps: u16,           // Share of the fee sent to the stakers' rewards pool
+    pub matcher_share_bps: u16,           // Share of the fee sent to the match's matcher
+    pub insurance_share_bps: u16,         // Share of the fee sent to the insurance fund
+    pub stake_mint: Pubkey,               // Mint arbitrators stake in
+    pub min_arbitrator_stake: u64,        // Stake required to be drawn for a panel
+    pub arbitrator_count: u32,            // Number of arbitrators ever registered
+    pub default_consumer_share: u8,       // Consumer share applied when a dispute times out
+    pub cancellation_fee_bps: u16,        // Fee on the unearned escrow charged to whoever terminates early
+    pub bump: u8,                         // Config PDA bump
+}
+
+impl Config {
+    pub const LEN: usize = 32 + 32 + 2 + 2 + 2 + 2 + 32 + 8 + 4 + 1 + 2 + 1;
+}
+
+#[account]
+pub struct AllowedMint {
+    pub mint: Pubkey,                     // Allow-listed payment mint
+    pub treasury: Pubkey,                 // Treasury token account (owned by the config)
+    pub insurance_fund: Pubkey,           // Insurance fund token account (owned by the config)
+    pub rewards_pool: Pubkey,             // Stakers' rewards pool token account
+    pub enabled: bool,                    // Whether new escrows may use this mint
+    pub panel_threshold: u64,             // Disputes over this amount go to a panel (0 = never)
+    pub coverage_limit: u64,              // Most a single insurance claim pays out (0 = no cover)
+    pub bump: u8,                         // AllowedMint PDA bump
+}
+
+impl AllowedMint {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 1 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct InsuranceClaim {
+    pub escrow: Pubkey,                   // Escrow of the failed match
+    pub consumer: Pubkey,                 // Claimant
+    pub mint: Pubkey,                     // Mint the claim is paid in
+    pub amount: u64,                      // Amount claimed
+    pub status: ClaimStatus,              // Current status
+    pub reviewed_by: Pubkey,              // Reviewing arbitrator
+    pub bump: u8,                         // InsuranceClaim PDA bump
+    pub created_at: i64,                  // Filing timestamp
+    pub updated_at: i64,                  // Last update timestamp
+}
+
+impl InsuranceClaim {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 1 + 32 + 1 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    Rejected,
+}
+
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// Upper bound on the platform fee (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+// Upper bound on the early termination fee (20%)
+pub const MAX_CANCELLATION_FEE_BPS: u16 = 2_000;
+
+#[account]
+pub struct Arbitrator {
+    pub authority: Pubkey,                // Key that signs as arbitrator
+    pub index: u32,                       // Registration index used for panel draws
+    pub active: bool,                     // Whether new disputes can be assigned
+    pub stake: u64,                       // Amount staked in stake_mint
+    pub stake_vault: Pubkey,              // Token account holding the stake (owned by the config)
+    pub active_panels: u16,               // Panels the arbitrator currently sits on
+    pub resolved_count: u32,              // Number of disputes resolved
+    pub slashed_count: u32,               // Number of times slashed for voting against consensus
+    pub missed_count: u32,                // Number of disputes left undecided past the deadline
+    pub bump: u8,                         // Arbitrator PDA bump
+    pub created_at: i64,                  // Registration timestamp
+}
+
+impl Arbitrator {
+    pub const LEN: usize = 32 + 4 + 1 + 8 + 32 + 2 + 4 + 4 + 4 + 1 + 8;
+}
+
+#[account]
+pub struct Dispute {
+    pub escrow: Pubkey,                   // Disputed escrow
+    pub claimant: Pubkey,                 // Party who opened the dispute
+    pub reason_code: DisputeReason,       // Category of the complaint
+    pub reason: String,                   // Claimant's description
+    pub evidence: Vec<Evidence>,          // Evidence submitted by either party
+    pub evidence_deadline: i64,           // Evidence is accepted until this time
+    pub resolution_deadline: i64,         // Default resolution applies after this time
+    pub ruling_consumer_share: u8,        // Consumer share of the first-round ruling
+    pub ruled_by: Pubkey,                 // Arbitrator or panel that issued the ruling
+    pub appeal_deadline: i64,             // The ruling may be appealed until this time
+    pub round: u8,                        // 0 before an appeal, 1 once appealed
+    pub appellant: Pubkey,                // Party who appealed
+    pub appeal_bond: u64,                 // Bond posted by the appellant
+    pub bump: u8,                         // Dispute PDA bump
+    pub created_at: i64,                  // Dispute timestamp
+    pub draw_slot: u64,                   // Slot whose hash seeds the current round's panel draw
+    pub milestone: Option<u8>,            // Disputed milestone (None for a whole-escrow dispute)
+}
+
+impl Dispute {
+    pub const LEN: usize = 32 + 32 + 1 + 4 + MAX_DISPUTE_REASON_LEN
+        + 4 + MAX_EVIDENCE * Evidence::LEN + 8 + 8 + 1 + 32 + 8 + 1 + 32 + 8 + 1 + 8 + 8 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Evidence {
+    pub content_hash: [u8; 32],           // Hash of the evidence content
+    pub uri: String,                      // Where the content can be fetched
+    pub submitter: Pubkey,                // Party who submitted it
+    pub submitted_at: i64,                // Submission timestamp
+}
+
+impl Evidence {
+    pub const LEN: usize = 32 + 4 + MAX_EVIDENCE_URI_LEN + 32 + 8;
 }
 
-#[derive(Accounts)]
-pub struct CreateEscrow<'info> {
-    #[account(init, payer = consumer, space = 8 + Escrow::LEN)]
-    pub escrow: Account<'info, Escrow>,
-    #[account(mut)]
-    pub consumer: Signer<'info>,
-    #[account(mut)]
-    pub consumer_token_account: Account<'info, TokenAccount>,
-    /// CHECK: We only use this for recording the provider
-    pub provider: AccountInfo<'info>,
-    #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeReason {
+    ServiceNotProvided,
+    ServiceDegraded,
+    IncorrectResults,
+    PaymentNotReleased,
+    Other,
 }
 
-#[derive(Accounts)]
-pub struct ReleaseFunds<'info> {
-    #[account(mut)]
-    pub escrow: Account<'info, Escrow>,
-    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
-    /// CHECK: This is a PDA that manages the escrow
-    pub escrow_authority: AccountInfo<'info>,
-    #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub provider_token_account: Account<'info, TokenAccount>,
-    pub signer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+// Longest dispute description stored on-chain
+pub const MAX_DISPUTE_REASON_LEN: usize = 200;
+
+// Maximum number of evidence entries per dispute
+pub const MAX_EVIDENCE: usize = 10;
+
+// Maximum number of evidence entries from each party
+pub const MAX_EVIDENCE_PER_PARTY: usize = MAX_EVIDENCE / 2;
+
+// Longest evidence URI
+pub const MAX_EVIDENCE_URI_LEN: usize = 128;
+
+// Time both parties have to submit evidence after a dispute opens
+pub const EVIDENCE_PERIOD: i64 = 3 * 24 * 60 * 60;
+
+// Time arbitrators have to decide after the evidence period closes
+pub const ARBITRATION_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+// Time a provider has to start a match after its scheduled start
+pub const NO_SHOW_GRACE_PERIOD: i64 = 60 * 60;
+
+// Time the losing party has to appeal a ruling
+pub const APPEAL_WINDOW: i64 = 2 * 24 * 60 * 60;
+
+// Appeal bond as a portion of the disputed amount
+pub const APPEAL_BOND_BPS: u16 = 1_000;
+
+#[account]
+pub struct DisputePanel {
+    pub escrow: Pubkey,                   // Disputed escrow
+    pub round: u8,                        // Dispute round the panel was drawn for
+    pub members: Vec<PanelMember>,        // Drawn arbitrators and their votes
+    pub voting_deadline: i64,             // Votes are accepted until this time
+    pub finalized: bool,                  // Whether the outcome has been applied
+    pub bump: u8,                         // DisputePanel PDA bump
+    pub created_at: i64,                  // Draw timestamp
 }
 
-#[derive(Accounts)]
-pub struct RefundEscrow<'info> {
-    #[account(mut)]
-    pub escrow: Account<'info, Escrow>,
-    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
-    /// CHECK: This is a PDA that manages the escrow
-    pub escrow_authority: AccountInfo<'info>,
-    #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub consumer_token_account: Account<'info, TokenAccount>,
-    pub signer: Signer<'info>,
-    /// CHECK: This is a trusted arbitrator account
-    pub arbitrator: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+impl DisputePanel {
+    pub const LEN: usize = 32 + 1 + 4 + MAX_PANEL_SIZE * PanelMember::LEN + 8 + 1 + 1 + 8;
 }
 
-#[derive(Accounts)]
-pub struct DisputeEscrow<'info> {
-    #[account(mut)]
-    pub escrow: Account<'info, Escrow>,
-    pub signer: Signer<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PanelMember {
+    pub arbitrator: Pubkey,               // Arbitrator authority
+    pub consumer_share: u8,               // Voted consumer share (0-100)
+    pub voted: bool,                      // Whether the member has voted
 }
 
-#[derive(Accounts)]
-pub struct ResolveDispute<'info> {
-    #[account(mut)]
-    pub escrow: Account<'info, Escrow>,
-    #[account(seeds = [b"escrow".as_ref(), escrow.key().as_ref()], bump)]
-    /// CHECK: This is a PDA that manages the escrow
-    pub escrow_authority: AccountInfo<'info>,
-    #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub consumer_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub provider_token_account: Account<'info, TokenAccount>,
-    pub arbitrator: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+impl PanelMember {
+    pub const LEN: usize = 32 + 1 + 1;
 }
 
+// Number of arbitrators drawn for a panel
+pub const PANEL_SIZE: usize = 3;
+
+// Number of arbitrators drawn for an appeal
+pub const APPEAL_PANEL_SIZE: usize = 5;
+
+// Largest panel an account is sized for
+pub const MAX_PANEL_SIZE: usize = 5;
+
+// Candidates that may be examined while drawing a panel
+pub const MAX_DRAW_ATTEMPTS: usize = 16;
+
+// Slots between filing a panel dispute or appeal and the slot whose hash
+// seeds the draw
+pub const PANEL_DRAW_DELAY_SLOTS: u64 = 32;
+
+// Time panel members have to vote
+pub const PANEL_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;
+
+// Votes further than this from the outcome (in share points) are slashed
+pub const CONSENSUS_TOLERANCE: u8 = 10;
+
+// Portion of stake slashed for voting against consensus
+pub const SLASH_BPS: u16 = 1_000;
+
 #[account]
 pub struct Escrow {
     pub match_id: Pubkey,                 // Related match ID
     pub consumer: Pubkey,                 // Consumer address
     pub provider: Pubkey,                 // Provider address
+    pub mint: Pubkey,                     // Mint the escrow is held in
     pub amount: u64,                      // Escrow amount
+    pub released_amount: u64,             // Amount already paid out of the escrow
+    pub refunded_amount: u64,             // Part of the payouts returned to the consumer
     pub release_time: i64,                // Time when funds can be auto-released
+    pub mode: EscrowMode,                 // How funds are paid out
+    pub stream_start: i64,                // Planned match start time
+    pub stream_end: i64,                  // Planned match end time; the gap sets the accrual length
+    pub milestones: Vec<Milestone>,       // Payment milestones (milestone mode only)
+    pub arbitrator: Pubkey,               // Arbitrator assigned at the first dispute
+    pub panel_dispute: bool,              // Whether the dispute is decided by a panel
+    pub extension_pending: bool,          // Whether the vault holds a pending extension's top-up
     pub status: EscrowStatus,             // Current status
     pub created_at: i64,                  // Creation timestamp
     pub updated_at: i64,                  // Last update timestamp
 }
 
 impl Escrow {
-    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1 + 8 + 8;
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8
+        + 4 + MAX_MILESTONES * Milestone::LEN
+        + 32 + 1 + 1 + 1 + 8 + 8;
+
+    // Amount still held in the vault
+    pub fn remaining(&self) -> u64 {
+        self.amount - self.released_amount
+    }
+
+    // Amount earned by the provider at `now`, pro-rated over the length of
+    // the match window. Accrual runs from when the match actually started,
+    // so nothing accrues before the provider starts work.
+    pub fn accrued(&self, now: i64, actual_start: i64) -> u64 {
+        if actual_start == 0 || now <= actual_start {
+            return 0;
+        }
+        let duration = self.stream_end - self.stream_start;
+        let elapsed = (now - actual_start).min(duration);
+        (self.amount as u128 * elapsed as u128 / duration as u128) as u64
+    }
+
+    // Whether every milestone has been released or resolved
+    pub fn milestones_settled(&self) -> bool {
+        self.milestones.iter().all(|m| {
+            m.status == MilestoneStatus::Released || m.status == MilestoneStatus::Resolved
+        })
+    }
+}
+
+#[account]
+pub struct ExtensionProposal {
+    pub escrow: Pubkey,                   // Escrow being extended
+    pub new_end_time: i64,                // Proposed match end time
+    pub top_up: u64,                      // Payment for the extra time, held in the vault
+    pub bump: u8,                         // ExtensionProposal PDA bump
+    pub created_at: i64,                  // Proposal timestamp
+}
+
+impl ExtensionProposal {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 8;
+}
+
+// Maximum number of milestones per escrow
+pub const MAX_MILESTONES: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Milestone {
+    pub amount: u64,                      // Amount paid when the milestone is settled
+    pub description_hash: [u8; 32],       // Hash of the off-chain milestone description
+    pub deadline: i64,                    // After this time anyone can release the milestone
+    pub status: MilestoneStatus,          // Current status
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneParams {
+    pub amount: u64,
+    pub description_hash: [u8; 32],
+    pub deadline: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    Pending,
+    Released,
+    Disputed,
+    Resolved,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowMode {
+    Lump,
+    Streaming,
+    Milestone,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -393,6 +4804,198 @@ pub enum EscrowStatus {
     Refunded,
     Disputed,
     Resolved,
+    Ruled,
+    Terminated,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub usage_oracle: Pubkey,
+}
+
+#[event]
+pub struct AllowedMintAdded {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub rewards_pool: Pubkey,
+}
+
+#[event]
+pub struct AllowedMintUpdated {
+    pub mint: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct ArbitratorUpdated {
+    pub authority: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct ArbitratorStakeUpdated {
+    pub authority: Pubkey,
+    pub stake: u64,
+}
+
+#[event]
+pub struct ArbitratorSlashed {
+    pub authority: Pubkey,
+    pub escrow_id: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PanelDrawn {
+    pub escrow_id: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub voting_deadline: i64,
+}
+
+#[event]
+pub struct PanelDrawRescheduled {
+    pub escrow_id: Pubkey,
+    pub round: u8,
+    pub draw_slot: u64,
+}
+
+#[event]
+pub struct PanelVoteCast {
+    pub escrow_id: Pubkey,
+    pub arbitrator: Pubkey,
+    pub consumer_share: u8,
+}
+
+#[event]
+pub struct ExtensionProposed {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub new_end_time: i64,
+    pub top_up: u64,
+}
+
+#[event]
+pub struct ExtensionAccepted {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub new_end_time: i64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ExtensionCancelled {
+    pub escrow_id: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub top_up: u64,
+}
+
+#[event]
+pub struct CoverageLimitUpdated {
+    pub mint: Pubkey,
+    pub coverage_limit: u64,
+}
+
+#[event]
+pub struct InsuranceClaimFiled {
+    pub claim_id: Pubkey,
+    pub escrow_id: Pubkey,
+    pub consumer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct InsuranceClaimReviewed {
+    pub claim_id: Pubkey,
+    pub reviewed_by: Pubkey,
+    pub approved: bool,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MatchTerminatedEarly {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub terminated_by: Pubkey,
+    pub provider_amount: u64,
+    pub consumer_amount: u64,
+    pub cancellation_fee: u64,
+}
+
+#[event]
+pub struct CancellationFeeUpdated {
+    pub cancellation_fee_bps: u16,
+}
+
+#[event]
+pub struct NoShowClaimed {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RulingIssued {
+    pub escrow_id: Pubkey,
+    pub ruled_by: Pubkey,
+    pub consumer_share: u8,
+    pub provider_share: u8,
+    pub appeal_deadline: i64,
+}
+
+#[event]
+pub struct RulingAppealed {
+    pub escrow_id: Pubkey,
+    pub appellant: Pubkey,
+    pub appeal_bond: u64,
+    pub resolution_deadline: i64,
+}
+
+#[event]
+pub struct AppealBondSettled {
+    pub escrow_id: Pubkey,
+    pub appellant: Pubkey,
+    pub amount: u64,
+    pub returned: bool,
+}
+
+#[event]
+pub struct ArbitratorMissedDeadline {
+    pub authority: Pubkey,
+    pub escrow_id: Pubkey,
+}
+
+#[event]
+pub struct DefaultResolutionUpdated {
+    pub default_consumer_share: u8,
+}
+
+#[event]
+pub struct FeeConfigUpdated {
+    pub fee_bps: u16,
+    pub rewards_share_bps: u16,
+    pub matcher_share_bps: u16,
+    pub insurance_share_bps: u16,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct FeeCollected {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub rewards_amount: u64,
+    pub matcher_amount: u64,
+    pub insurance_amount: u64,
+    pub treasury_amount: u64,
 }
 
 #[event]
@@ -401,6 +5004,7 @@ pub struct EscrowCreated {
     pub match_id: Pubkey,
     pub consumer: Pubkey,
     pub provider: Pubkey,
+    pub mint: Pubkey,
     pub amount: u64,
 }
 
@@ -420,12 +5024,82 @@ pub struct EscrowRefunded {
     pub refunded_by: Pubkey,
 }
 
+#[event]
+pub struct AccruedWithdrawn {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub amount: u64,
+    pub released_amount: u64,
+}
+
+#[event]
+pub struct StreamStopped {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub provider_amount: u64,
+    pub consumer_amount: u64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+    pub released_by: Pubkey,
+}
+
+#[event]
+pub struct MilestoneDisputed {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub index: u8,
+    pub disputed_by: Pubkey,
+    pub arbitrator: Pubkey,
+    pub reason_code: DisputeReason,
+    pub reason: String,
+    pub evidence_deadline: i64,
+    pub resolution_deadline: i64,
+}
+
+#[event]
+pub struct MilestoneDisputeResolved {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub index: u8,
+    pub resolved_by: Pubkey,
+    pub consumer_share: u8,
+    pub provider_share: u8,
+}
+
+#[event]
+pub struct UsageSettled {
+    pub escrow_id: Pubkey,
+    pub match_id: Pubkey,
+    pub hours_used: u64,
+    pub provider_amount: u64,
+    pub consumer_amount: u64,
+    pub attested_by: Pubkey,
+}
+
 #[event]
 pub struct EscrowDisputed {
     pub escrow_id: Pubkey,
     pub match_id: Pubkey,
     pub disputed_by: Pubkey,
+    pub arbitrator: Pubkey,
+    pub reason_code: DisputeReason,
     pub reason: String,
+    pub evidence_deadline: i64,
+    pub resolution_deadline: i64,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub escrow_id: Pubkey,
+    pub submitter: Pubkey,
+    pub content_hash: [u8; 32],
+    pub uri: String,
 }
 
 #[event]
@@ -445,7 +5119,7 @@ pub enum TransactionError {
     InvalidReleaseTime,
     #[msg("Only authorized parties can release the funds")]
     UnauthorizedRelease,
-    #[msg("Only the provider or arbitrator can refund the escrow")]
+    #[msg("Only the provider or a registered arbitrator can refund the escrow")]
     UnauthorizedRefund,
     #[msg("Only the consumer or provider can dispute the escrow")]
     UnauthorizedDispute,
@@ -453,4 +5127,160 @@ pub enum TransactionError {
     InvalidEscrowStatus,
     #[msg("Consumer and provider shares must add up to 100")]
     InvalidShares,
+    #[msg("Match account does not belong to this escrow")]
+    MatchMismatch,
+    #[msg("Escrow is frozen while the match is disputed")]
+    MatchDisputed,
+    #[msg("Only the match consumer can fund its escrow")]
+    UnauthorizedEscrowCreation,
+    #[msg("Escrow can only be created for a confirmed match")]
+    MatchNotConfirmed,
+    #[msg("Token account mint does not match the escrow mint")]
+    InvalidMint,
+    #[msg("Consumer account does not match the escrow")]
+    ConsumerMismatch,
+    #[msg("Provider token account does not belong to the escrow provider")]
+    ProviderMismatch,
+    #[msg("Only the provider can withdraw accrued funds")]
+    UnauthorizedWithdrawal,
+    #[msg("Only the consumer can stop the stream")]
+    UnauthorizedStreamStop,
+    #[msg("Escrow is not in streaming mode")]
+    NotStreamingEscrow,
+    #[msg("No funds have accrued since the last withdrawal")]
+    NothingAccrued,
+    #[msg("Milestones must be non-empty, positive and sum to the escrow amount")]
+    InvalidMilestones,
+    #[msg("Milestone escrows must be released per milestone")]
+    MilestoneEscrow,
+    #[msg("Escrow is not in milestone mode")]
+    NotMilestoneEscrow,
+    #[msg("Milestone index is out of range")]
+    InvalidMilestoneIndex,
+    #[msg("Milestone is not in the correct status for this operation")]
+    InvalidMilestoneStatus,
+    #[msg("Only the provider can settle usage")]
+    UnauthorizedSettlement,
+    #[msg("Usage must be co-signed by the consumer or attested by the usage oracle")]
+    InvalidUsageAttestation,
+    #[msg("Escrow mode does not support this operation")]
+    InvalidEscrowMode,
+    #[msg("The match has not finished yet")]
+    MatchNotFinished,
+    #[msg("Resource account does not belong to this match")]
+    ResourceMismatch,
+    #[msg("Only the admin can perform this action")]
+    UnauthorizedAdmin,
+    #[msg("Fee or fee shares exceed the allowed maximum")]
+    InvalidFee,
+    #[msg("Fee account does not match the configured treasury accounts")]
+    TreasuryMismatch,
+    #[msg("Matcher token account does not belong to the match's matcher")]
+    MatcherMismatch,
+    #[msg("Payment mint is not on the allow-list")]
+    MintNotAllowed,
+    #[msg("A consumer token account is required for SPL token escrows")]
+    MissingTokenAccount,
+    #[msg("Arbitrator is not active")]
+    InactiveArbitrator,
+    #[msg("Arbitrator cannot be a party to the escrow")]
+    ConflictedArbitrator,
+    #[msg("Signer is not the arbitrator assigned to this escrow")]
+    ArbitratorMismatch,
+    #[msg("No eligible arbitrator was drawn for this dispute")]
+    MissingArbitrator,
+    #[msg("This dispute must be decided by a panel")]
+    PanelDispute,
+    #[msg("Arbitration has already been initialized")]
+    ArbitrationAlreadyInitialized,
+    #[msg("Arbitration has not been initialized")]
+    ArbitrationNotInitialized,
+    #[msg("Stake vault does not belong to this arbitrator")]
+    StakeVaultMismatch,
+    #[msg("Arbitrator cannot unstake while sitting on a panel")]
+    ArbitratorOnPanel,
+    #[msg("Not enough eligible arbitrators were drawn for the panel")]
+    PanelIncomplete,
+    #[msg("Candidate does not match the drawn arbitrator index")]
+    PanelDrawMismatch,
+    #[msg("Panel voting has closed")]
+    PanelVotingClosed,
+    #[msg("Panel voting is still open")]
+    PanelVotingOpen,
+    #[msg("Not enough panel members have voted")]
+    PanelQuorumNotReached,
+    #[msg("Signer is not a member of this panel")]
+    NotPanelMember,
+    #[msg("Panel member has already voted")]
+    AlreadyVoted,
+    #[msg("Remaining accounts do not match the panel members")]
+    InvalidPanelAccounts,
+    #[msg("Dispute reason is too long")]
+    DisputeReasonTooLong,
+    #[msg("Only the consumer or provider can submit evidence")]
+    UnauthorizedEvidence,
+    #[msg("Evidence period has closed")]
+    EvidencePeriodClosed,
+    #[msg("Evidence URI is too long")]
+    EvidenceUriTooLong,
+    #[msg("Party has already submitted the maximum evidence entries")]
+    TooMuchEvidence,
+    #[msg("Dispute resolution deadline has not been reached")]
+    DisputeDeadlineNotReached,
+    #[msg("Appeal window is still open")]
+    AppealWindowOpen,
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("This dispute has already been appealed")]
+    AppealAlreadyFiled,
+    #[msg("Only the losing party can appeal")]
+    UnauthorizedAppeal,
+    #[msg("Token account does not belong to the appellant")]
+    AppellantMismatch,
+    #[msg("Match has already started or moved past confirmation")]
+    MatchAlreadyStarted,
+    #[msg("No-show grace period has not passed")]
+    NoShowGracePeriod,
+    #[msg("Match is not confirmed or in progress")]
+    MatchNotActive,
+    #[msg("Extension must move the end time later")]
+    InvalidExtension,
+    #[msg("Extension exceeds the resource's maximum rental time")]
+    ExceedsMaxRentalTime,
+    #[msg("Only the escrow's parties can manage an extension")]
+    UnauthorizedExtension,
+    #[msg("Only the consumer or provider can terminate the match")]
+    UnauthorizedTermination,
+    #[msg("Match did not fail through a dispute ruling or a rejected replica")]
+    ClaimNotEligible,
+    #[msg("Claim exceeds the insurance coverage")]
+    ExceedsCoverage,
+    #[msg("Insurance claim has already been reviewed")]
+    InvalidClaimStatus,
+    #[msg("Escrow does not match the insurance claim")]
+    ClaimMismatch,
+    #[msg("Replication group does not match the match record")]
+    ReplicationGroupMismatch,
+    #[msg("Result has not been confirmed by a replica quorum")]
+    ResultNotVerified,
+    #[msg("The match has not started yet")]
+    MatchNotStarted,
+    #[msg("The consumer can still challenge the reported completion")]
+    ChallengeWindowOpen,
+    #[msg("Dispute account is required")]
+    MissingDispute,
+    #[msg("The slot seeding the panel draw has not been reached")]
+    PanelDrawPending,
+    #[msg("The slot seeding the panel draw is no longer recorded; reschedule the draw")]
+    PanelSeedExpired,
+    #[msg("A panel has already been drawn for this round")]
+    PanelAlreadyDrawn,
+    #[msg("The evidence period is still open")]
+    EvidencePeriodOpen,
+    #[msg("Dispute does not belong to this escrow")]
+    DisputeMismatch,
+    #[msg("The pending extension proposal is required")]
+    MissingExtension,
+    #[msg("Resource is offline or under maintenance")]
+    ResourceUnavailable,
 } 
\ No newline at end of file